@@ -0,0 +1,59 @@
+use bank_of_italy_api::{BancaDItalia, LatestRateAPI};
+use criterion::{criterion_group, criterion_main, Criterion};
+use serde_json::value::RawValue;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::hint::black_box;
+
+/// Builds a `/latestRates`-shaped payload with the given number of records.
+fn large_payload(records: usize) -> String {
+    let rates = (0..records)
+        .map(|i| {
+            format!(
+                r#"{{"country":"COUNTRY {i}","currency":"Currency {i}","isoCode":"C{i:02}","uicCode":"{i}","eurRate":"{i}.1234","usdRate":"{i}.5678","usdExchangeConvention":"Quantity of currency for 1 Dollar","usdExchangeConventionCode":"C","referenceDate":"2025-07-30"}}"#
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    format!(
+        r#"{{"resultsInfo":{{"totalRecords":{records},"timezoneReference":"Dates refer to the Central European Time Zone","notice":""}},"latestRates":[{rates}]}}"#
+    )
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let payload = large_payload(50_000);
+    let path = std::env::temp_dir().join("bank_of_italy_api_bench_latest_rates.json");
+    std::fs::write(&path, &payload).unwrap();
+
+    let mut group = c.benchmark_group("latest_rates_50k");
+    group.bench_function("value_round_trip", |b| {
+        b.iter(|| {
+            let response = serde_json::from_slice::<Value>(payload.as_bytes()).unwrap();
+            let data = response
+                .get("latestRates")
+                .and_then(Value::as_array)
+                .unwrap();
+            let rates: Vec<LatestRateAPI> =
+                serde_json::from_value(Value::Array(data.to_owned())).unwrap();
+            black_box(rates)
+        })
+    });
+    group.bench_function("raw_value_envelope", |b| {
+        b.iter(|| {
+            let mut envelope =
+                serde_json::from_slice::<HashMap<String, &RawValue>>(payload.as_bytes()).unwrap();
+            let data = envelope.remove("latestRates").unwrap();
+            let rates: Vec<LatestRateAPI> = serde_json::from_str(data.get()).unwrap();
+            black_box(rates)
+        })
+    });
+    group.bench_function("latest_rate_from_file", |b| {
+        b.iter(|| black_box(BancaDItalia::latest_rate_from_file(&path).unwrap()))
+    });
+    group.finish();
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+criterion_group!(benches, bench_parse);
+criterion_main!(benches);
@@ -0,0 +1,65 @@
+//! # Clock
+//!
+//! This module abstracts the current time used by the client. Time-dependent behavior, such as recording the last
+//! successful fetch or scheduling the smart poll, reads the time through a `Clock`, so it can be tested
+//! deterministically with a `FixedClock` instead of sleeping.
+
+use std::sync::Mutex;
+use time::{Duration, OffsetDateTime};
+
+/// A source of the current time.
+pub trait Clock: Send + Sync {
+    /// Returns the current time.
+    fn now(&self) -> OffsetDateTime;
+}
+
+/// The clock reading the system time, used by default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> OffsetDateTime {
+        OffsetDateTime::now_utc()
+    }
+}
+
+/// A clock returning a time set by hand, for tests.
+///
+/// ## Example
+/// ```rust
+/// use bank_of_italy_api::{Clock, FixedClock};
+/// use time::macros::datetime;
+///
+/// let clock = FixedClock::new(datetime!(2025-07-30 14:00 UTC));
+/// clock.advance(time::Duration::minutes(5));
+/// assert_eq!(clock.now(), datetime!(2025-07-30 14:05 UTC));
+/// ```
+#[derive(Debug)]
+pub struct FixedClock {
+    now: Mutex<OffsetDateTime>,
+}
+
+impl FixedClock {
+    /// Creates a clock stopped at `now`.
+    pub fn new(now: OffsetDateTime) -> Self {
+        FixedClock {
+            now: Mutex::new(now),
+        }
+    }
+
+    /// Sets the time returned by the clock.
+    pub fn set(&self, now: OffsetDateTime) {
+        *self.now.lock().expect("the clock lock is never poisoned") = now;
+    }
+
+    /// Moves the clock forward by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().expect("the clock lock is never poisoned") += duration;
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> OffsetDateTime {
+        *self.now.lock().expect("the clock lock is never poisoned")
+    }
+}
@@ -6,6 +6,11 @@
 //! - Fetch supported currencies and their associated countries.
 //! - Retrieve the latest exchange rates in EUR and USD.
 //! - Automatic deserialization into strongly-typed Rust structs.
+//! - Optional `chrono` accessors for dates, behind the `chrono` feature.
+//! - Optional `reqwest-middleware` integration, behind the `reqwest-middleware` feature.
+//! - Optional currency symbols, behind the `symbols` feature.
+//! - An in-memory `MockTransport` for offline tests, behind the `test-util` feature.
+//! - US dollar rates and conventions, behind the default `usd` feature.
 //!
 //! ## Example Usage
 //! ```rust
@@ -18,24 +23,119 @@
 //!     println!("{:#?}", currencies);
 //! }
 //! ```
+use arc_swap::ArcSwapOption;
+use bytes::Bytes;
 use date_utils::{parse_to_datetime, DateTimeError, DateType, OffsetType};
 use reqwest::Client;
 use rust_decimal::Decimal;
-use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use serde_json::Value;
+use serde::{
+    de::{self, DeserializeOwned, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use serde_json::value::RawValue;
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
 use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use thiserror::Error;
-use time::Date;
+use time::{Date, OffsetDateTime, Time, UtcOffset, Weekday};
+use tokio::sync::Semaphore;
+use tokio::task::JoinHandle;
+
+mod clock;
+#[cfg(feature = "test-util")]
+mod mock;
+mod query;
+#[cfg(feature = "symbols")]
+mod symbols;
+mod validate;
+
+pub use clock::{Clock, FixedClock, SystemClock};
+#[cfg(feature = "test-util")]
+pub use mock::MockTransport;
+pub use query::QueryBuilder;
+
+/// Re-exports the client, the error type and all public data structures.
+///
+/// ## Example
+/// ```rust
+/// use bank_of_italy_api::prelude::*;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), BancaDItaliaError> {
+///     let boi = BancaDItalia::new()?;
+///     let latest_rates: Vec<LatestRate> = boi.get_latest_rate().await?;
+///     println!("{:#?}", latest_rates);
+///     Ok(())
+/// }
+/// ```
+pub mod prelude {
+    pub use crate::{
+        BancaDItalia, BancaDItaliaBuilder, BancaDItaliaError, Clock, Country, CountryAPI,
+        Currencies, Currency, CurrencyAPI, Endpoint, EndpointCheck, EndpointInfo, EndpointOutcome,
+        FixedClock, FormattedPayload, LatestRate, LatestRateAPI, MetaData, Notice,
+        PublicationSchedule, QueryBuilder, Quote, RateAnomaly, RateAnomalyKind, RatePair,
+        RatesReport, Reconciliation, RefreshHandle, RegistryDiff, RegistryRow, RequestHook,
+        RequestMetrics, ResponseFormat, ResultInfo, SelfTestReport, Staleness, SystemClock,
+        UicCode, UsdExchangeConvention,
+    };
+}
 
 /// Represent the Bank of Italy API base url.
 const BOI_BASE_URL: &str = "https://tassidicambio.bancaditalia.it/terzevalute-wf-web/rest/v1.0";
 
+/// The environment variable overriding the Bank of Italy API base url.
+///
+/// The variable is read when the client is built, so test and staging environments can redirect requests to a mock
+/// server without code changes. A base url set with `BancaDItaliaBuilder::base_url` takes precedence over it.
+pub const BASE_URL_ENV: &str = "BOI_BASE_URL";
+
+/// The default maximum number of requests in flight at the same time.
+pub const DEFAULT_MAX_CONCURRENCY: usize = 4;
+
+/// The default largest plausible rate used by rate anomaly detection.
+///
+/// The weakest currencies quoted by Banca d'Italia trade at around 10^5 units per euro, so one million leaves ample
+/// headroom while still catching misplaced decimal separators.
+pub const DEFAULT_MAX_PLAUSIBLE_RATE: Decimal = Decimal::from_parts(1_000_000, 0, 0, false, 0);
+
+/// The JSON field names consumed from each record of the `/latestRates` endpoint.
+pub const LATEST_RATE_FIELDS: &[&str] = &[
+    "country",
+    "currency",
+    "isoCode",
+    "uicCode",
+    "eurRate",
+    "usdRate",
+    "usdExchangeConvention",
+    "usdExchangeConventionCode",
+    "referenceDate",
+];
+
+/// The JSON field names consumed from each record of the `/currencies` endpoint.
+pub const CURRENCY_FIELDS: &[&str] = &["countries", "isoCode", "name", "graph"];
+
+/// The JSON field names consumed from each entry of a currency's `countries` array.
+pub const COUNTRY_FIELDS: &[&str] = &[
+    "currencyISO",
+    "country",
+    "countryISO",
+    "validityStartDate",
+    "validityEndDate",
+];
+
 /// Generates the URL for fetching the list of currencies.
 ///
 /// This macro expands to a `String` containing the full URL to the `/currencies` endpoint.
 macro_rules! currencies_url {
-    () => {
-        format!("{}/currencies?lang=en", BOI_BASE_URL)
+    ($base_url:expr) => {
+        format!(
+            "{}{}?{}",
+            $base_url,
+            Endpoint::Currencies.path(),
+            QueryBuilder::new().lang("en")
+        )
     };
 }
 
@@ -43,8 +143,13 @@ macro_rules! currencies_url {
 ///
 /// This macro expands to a `String` containing the full URL to the `/latestRates` endpoint.
 macro_rules! latestrate_url {
-    () => {
-        format!("{}/latestRates?lang=en", BOI_BASE_URL)
+    ($base_url:expr) => {
+        format!(
+            "{}{}?{}",
+            $base_url,
+            Endpoint::LatestRates.path(),
+            QueryBuilder::new().lang("en")
+        )
     };
 }
 
@@ -60,12 +165,72 @@ pub enum BancaDItaliaError {
     /// The API returned an error in its payload.
     #[error("Banca d'Italia returned api error: {0}")]
     ApiError(String),
-    /// No data was returned.
+    /// The access key is present but holds `null` instead of a dataset. An empty array is returned as an empty `Vec`,
+    /// and a missing access key is reported as `MissingKey`.
     #[error("Banca d'Italia API returned an empty dataset.")]
     NoResult,
     /// Failed to convert Strpping into Decimal
     #[error("Failed to convert String type into Decimal: {0}")]
     ConversionFailed(#[from] rust_decimal::Error),
+    /// Failed to read or write a local file.
+    #[error("I/O operation failed: {0}")]
+    Io(#[from] std::io::Error),
+    /// The requested currency is not listed in the data.
+    #[error("Currency not found: {0}")]
+    CurrencyNotFound(String),
+    /// The requested currency name matches several currencies.
+    #[error("Currency name {name:?} is ambiguous, candidates: {}", .candidates.join(", "))]
+    AmbiguousCurrency {
+        name: String,
+        candidates: Vec<String>,
+    },
+    /// The rate of the requested currency is not available.
+    #[error("Rate {field} is not available for currency {iso}")]
+    RateUnavailable { iso: String, field: String },
+    /// Failed to convert a rate field into Decimal.
+    #[error("Failed to convert field {field} with value {value:?} into Decimal")]
+    DecimalParse { field: String, value: String },
+    /// The response does not contain the expected access key.
+    #[error("Banca d'Italia response has no {key:?} key, found: {available_keys:?}")]
+    MissingKey {
+        key: String,
+        available_keys: Vec<String>,
+    },
+    /// A middleware of the `reqwest-middleware` stack failed.
+    #[cfg(feature = "reqwest-middleware")]
+    #[error("Request through middleware to Banca d'Italia API failed: {0}")]
+    Middleware(#[from] reqwest_middleware::Error),
+    /// The rates contain implausible values.
+    #[error("Banca d'Italia returned {} implausible rates", .0.len())]
+    RateAnomalies(Vec<RateAnomaly>),
+    /// An argument is rejected before any request is sent.
+    #[error("Invalid argument {name}: {reason}")]
+    InvalidArgument { name: String, reason: String },
+    /// None of the configured base urls could be reached.
+    #[error("Banca d'Italia hosts unreachable ({}): {source}", .hosts.join(", "))]
+    HostsUnreachable {
+        hosts: Vec<String>,
+        #[source]
+        source: Box<BancaDItaliaError>,
+    },
+    /// A record contains a field the crate does not consume.
+    #[error("Banca d'Italia response has unknown field {field:?}")]
+    UnknownField { field: String },
+    /// The request was redirected away from the API, e.g. to a maintenance page.
+    #[error("Banca d'Italia request redirected to {to:?}")]
+    UnexpectedRedirect { to: String },
+    /// An amount exceeded the range of `Decimal` while being converted or summed.
+    #[error("Amount in {iso} overflowed the Decimal range")]
+    AmountOverflow { iso: String },
+    /// A hop of a conversion path failed.
+    #[error("Conversion hop {hop} from {from} to {to} failed: {source}")]
+    ConversionHop {
+        hop: usize,
+        from: String,
+        to: String,
+        #[source]
+        source: Box<BancaDItaliaError>,
+    },
 }
 
 impl From<DateTimeError> for BancaDItaliaError {
@@ -75,15 +240,48 @@ impl From<DateTimeError> for BancaDItaliaError {
 }
 
 /// A client for interacting with the Banca d'Italia exchange rate and currency information API.
+#[derive(Clone)]
 pub struct BancaDItalia {
     /// Represent the client that performs the connection to Banca d'Italia API.
-    client: Client,
+    client: HttpClient,
+    /// The base url of the Banca d'Italia API.
+    base_url: String,
+    /// The base urls tried in order when `base_url` cannot be reached.
+    fallback_base_urls: Vec<String>,
+    /// The latest rates snapshot shared with the auto-refresh task.
+    snapshot: Arc<ArcSwapOption<Vec<LatestRate>>>,
+    /// The time of the last successful request, shared by the clones of the client.
+    last_success: Arc<ArcSwapOption<OffsetDateTime>>,
+    /// The display names resolved by `resolve_currency_name`, keyed by iso code.
+    currency_names: Arc<ArcSwapOption<HashMap<String, String>>>,
+    /// The largest plausible rate, when implausible rates are rejected.
+    max_plausible_rate: Option<Decimal>,
+    /// Whether unavailable rates are reported as errors.
+    strict_rates: bool,
+    /// Whether the rates keep their original string representation.
+    keep_raw: bool,
+    /// Whether fields not consumed by the crate are reported as errors.
+    deny_unknown_fields: bool,
+    /// Whether the last known latest rates are served when a fetch fails.
+    serve_stale_on_error: bool,
+    /// The iso codes found in the latest rates but not in the registry, when collected.
+    orphan_codes: Option<Arc<Mutex<BTreeSet<String>>>>,
+    /// The hook invoked after each request.
+    on_request: Option<Arc<RequestHook>>,
+    /// The permits capping the requests in flight, shared by the clones of the client.
+    limiter: Arc<Semaphore>,
+    /// The publication schedule followed by `poll_latest_rate_smart`.
+    publication_schedule: PublicationSchedule,
+    /// The source of the current time.
+    clock: Arc<dyn Clock>,
 }
 
 impl BancaDItalia {
     /// Creates a new Banca d'Italia client.
     ///
-    /// The function creates a Banca d'Italia client using `Client` from `reqwest` crate.
+    /// The function creates a Banca d'Italia client using `Client` from `reqwest` crate with the default settings. Use
+    /// `BancaDItalia::builder` to customize the client. Requests are sent to the url in the `BOI_BASE_URL` environment
+    /// variable when it is set, to the Banca d'Italia API otherwise.
     ///
     /// ## Returns
     /// - `Ok(Self)`: Returns a BancaDItalia instance, which allows connection to Banca d'Italia servers.
@@ -101,10 +299,265 @@ impl BancaDItalia {
     /// }
     /// ```
     pub fn new() -> Result<Self, BancaDItaliaError> {
-        Ok(Self {
-            client: Client::builder()
-                .build()
-                .map_err(BancaDItaliaError::RequestFailed)?,
+        Self::builder().build()
+    }
+
+    /// Creates a builder to configure a Banca d'Italia client.
+    ///
+    /// ## Returns
+    /// - `BancaDItaliaBuilder`: A builder initialized with the default settings.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use bank_of_italy_api::{BancaDItalia, BancaDItaliaError};
+    /// use std::time::Duration;
+    ///
+    /// fn main() -> Result<(), BancaDItaliaError> {
+    ///     let boi = BancaDItalia::builder()
+    ///         .pool_idle_timeout(Duration::from_secs(300))
+    ///         .pool_max_idle_per_host(1)
+    ///         .build()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn builder() -> BancaDItaliaBuilder {
+        BancaDItaliaBuilder::default()
+    }
+
+    /// Creates a Banca d'Italia client sending requests through a `reqwest-middleware` stack.
+    ///
+    /// The function lets the client reuse existing middlewares for retries, caching or tracing. The connection options of
+    /// `BancaDItaliaBuilder` do not apply, since the wrapped `reqwest::Client` is already built: configure it before
+    /// wrapping it. Requires the `reqwest-middleware` feature.
+    ///
+    /// ## Arguments
+    /// - `client`: The client wrapped in its middleware stack.
+    /// - `base_url`: The base url of the API, `None` to use the Banca d'Italia one.
+    ///
+    /// ## Returns
+    /// - `Self`: Returns a BancaDItalia instance sending requests through the middleware stack.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use bank_of_italy_api::BancaDItalia;
+    ///
+    /// let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    /// let boi = BancaDItalia::with_middleware(client, None);
+    /// ```
+    #[cfg(feature = "reqwest-middleware")]
+    pub fn with_middleware(
+        client: reqwest_middleware::ClientWithMiddleware,
+        base_url: Option<String>,
+    ) -> Self {
+        let mut boi = Self::builder().finish(HttpClient::Middleware(client));
+        if let Some(base_url) = base_url {
+            boi.base_url = base_url.trim_end_matches('/').to_string();
+        }
+        boi
+    }
+
+    /// Creates a Banca d'Italia client answering requests from an in-memory transport.
+    ///
+    /// The function lets tests run against canned responses instead of the live API. Use
+    /// `BancaDItaliaBuilder::build_with_mock` to combine the transport with other options. Requires the `test-util`
+    /// feature.
+    ///
+    /// ## Arguments
+    /// - `transport`: The transport holding the canned responses.
+    ///
+    /// ## Returns
+    /// - `Self`: Returns a BancaDItalia instance that never contacts the network.
+    #[cfg(feature = "test-util")]
+    pub fn with_mock(transport: MockTransport) -> Self {
+        Self::builder().build_with_mock(transport)
+    }
+
+    /// Sends a request to an endpoint and returns the response as is.
+    ///
+    /// The function is the lowest-level escape hatch of the client: it builds the url from the base url, the endpoint
+    /// and the query parameters, and returns the response without checking its status or reading its body, so headers
+    /// and status can be inspected. The caller owns the body and chooses how to consume it. The request counts toward
+    /// `max_concurrency` only until the response headers are received, and it is not reported to the `on_request` hook.
+    ///
+    /// ## Arguments
+    /// - `endpoint`: The endpoint path relative to the base url, e.g. `latestRates`.
+    /// - `query`: The query parameters, e.g. `[("lang", "en")]`.
+    ///
+    /// ## Returns
+    /// - `Ok(reqwest::Response)`: The response, with its body not consumed yet.
+    /// - `Err(BancaDItaliaError)`: If the url is invalid or the request fails.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use bank_of_italy_api::BancaDItalia;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let boi = BancaDItalia::new().unwrap();
+    ///     let response = boi.raw_response("latestRates", &[("lang", "en")]).await.unwrap();
+    ///     println!("{:?}", response.headers().get("content-type"));
+    ///     let body = response.text().await.unwrap();
+    ///     println!("{}", body);
+    /// }
+    /// ```
+    pub async fn raw_response(
+        &self,
+        endpoint: &str,
+        query: &[(&str, &str)],
+    ) -> Result<reqwest::Response, BancaDItaliaError> {
+        validate::non_empty("endpoint", endpoint)?;
+        let url = reqwest::Url::parse_with_params(
+            &format!("{}/{}", self.base_url, endpoint.trim_start_matches('/')),
+            query,
+        )
+        .map_err(|err| BancaDItaliaError::InvalidArgument {
+            name: "endpoint".to_string(),
+            reason: err.to_string(),
+        })?;
+        let _permit = self
+            .limiter
+            .acquire()
+            .await
+            .expect("the request limiter is never closed");
+        self.send(url.as_str(), ResponseFormat::Json).await
+    }
+
+    /// Sends a GET request, falling back to the next base url when a host cannot be reached.
+    ///
+    /// ## Arguments
+    /// - `url`: The url to request, starting with the base url.
+    /// - `format`: The format requested through the `Accept` header.
+    ///
+    /// ## Returns
+    /// - `Ok(reqwest::Response)`: The response of the first reachable host, with its body not consumed yet.
+    /// - `Err(BancaDItaliaError)`: If the request fails, or `HostsUnreachable` if no host of the chain can be reached.
+    async fn send(
+        &self,
+        url: &str,
+        format: ResponseFormat,
+    ) -> Result<reqwest::Response, BancaDItaliaError> {
+        let path = match url.strip_prefix(self.base_url.as_str()) {
+            Some(path) if !self.fallback_base_urls.is_empty() => path,
+            _ => return self.client.get(url, format).await,
+        };
+        let mut hosts = Vec::new();
+        let mut last_error = None;
+        for base_url in std::iter::once(&self.base_url).chain(&self.fallback_base_urls) {
+            hosts.push(base_url.clone());
+            match self
+                .client
+                .get(&format!("{}{}", base_url, path), format)
+                .await
+            {
+                Err(err) if is_connection_failure(&err) => {
+                    tracing::warn!(host = %base_url, error = %err, "Banca d'Italia host unreachable");
+                    last_error = Some(err);
+                }
+                result => return result,
+            }
+        }
+        Err(BancaDItaliaError::HostsUnreachable {
+            hosts,
+            source: Box::new(last_error.expect("the chain has at least one host")),
+        })
+    }
+
+    /// Retrieves the raw response body from Banca d'Italia servers.
+    ///
+    /// The function reports every request, successful or not, to the `on_request` hook when one is configured, and records
+    /// the time of successful ones for `last_successful_fetch`.
+    ///
+    /// ## Arguments
+    /// - `url`: The url to data endpoint.
+    /// - `format`: The format requested through the `Accept` header.
+    ///
+    /// ## Returns
+    /// - `Ok(Bytes)`: The raw response body.
+    /// - `Err(BancaDItaliaError)`: If the request fails.
+    async fn get_payload(
+        &self,
+        url: &str,
+        format: ResponseFormat,
+    ) -> Result<Bytes, BancaDItaliaError> {
+        self.get_payload_with_status(url, format)
+            .await
+            .map(|(_, payload)| payload)
+    }
+
+    /// Retrieves the raw response body from Banca d'Italia servers, along with the HTTP status code.
+    ///
+    /// ## Arguments
+    /// - `url`: The url to data endpoint.
+    /// - `format`: The format requested through the `Accept` header.
+    ///
+    /// ## Returns
+    /// - `Ok((u16, Bytes))`: The HTTP status code and the raw response body.
+    /// - `Err(BancaDItaliaError)`: If the request fails.
+    async fn get_payload_with_status(
+        &self,
+        url: &str,
+        format: ResponseFormat,
+    ) -> Result<(u16, Bytes), BancaDItaliaError> {
+        let _permit = self
+            .limiter
+            .acquire()
+            .await
+            .expect("the request limiter is never closed");
+        let started = Instant::now();
+        let mut status = None;
+        let result: Result<(u16, Bytes), BancaDItaliaError> = async {
+            let response = self.send(url, format).await?;
+            let code = response.status().as_u16();
+            status = Some(code);
+            Ok((code, response.bytes().await?))
+        }
+        .await;
+
+        if result.is_ok() && status.is_some_and(|code| (200..300).contains(&code)) {
+            self.last_success.store(Some(Arc::new(self.clock.now())));
+        }
+        if let Some(on_request) = &self.on_request {
+            on_request(RequestMetrics {
+                endpoint: endpoint_name(url).to_string(),
+                duration: started.elapsed(),
+                status,
+                bytes: result.as_ref().map_or(0, |(_, payload)| payload.len()),
+            });
+        }
+        result
+    }
+
+    /// Retrieves a response body in the requested format.
+    ///
+    /// ## Arguments
+    /// - `url`: The url to data endpoint.
+    /// - `format`: The format requested through the `Accept` header.
+    ///
+    /// ## Returns
+    /// - `Ok(FormattedPayload)`: The body, decoded according to `format`.
+    /// - `Err(BancaDItaliaError)`: If the request fails, the response status is not a success, or the body is not valid
+    ///   JSON or UTF-8 text.
+    async fn get_formatted(
+        &self,
+        url: &str,
+        format: ResponseFormat,
+    ) -> Result<FormattedPayload, BancaDItaliaError> {
+        let (status, payload) = self.get_payload_with_status(url, format).await?;
+        if !(200..300).contains(&status) {
+            return Err(BancaDItaliaError::ApiError(format!(
+                "{} responded with status {}",
+                endpoint_name(url),
+                status
+            )));
+        }
+        Ok(match format {
+            ResponseFormat::Json => FormattedPayload::Json(serde_json::from_slice(&payload)?),
+            ResponseFormat::Csv => {
+                FormattedPayload::Csv(String::from_utf8(payload.to_vec()).map_err(|err| {
+                    BancaDItaliaError::ApiError(format!("CSV response is not valid UTF-8: {}", err))
+                })?)
+            }
+            ResponseFormat::Pdf => FormattedPayload::Pdf(payload.to_vec()),
         })
     }
 
@@ -126,29 +579,20 @@ impl BancaDItalia {
         url: &str,
         access_key: &str,
     ) -> Result<Vec<T>, BancaDItaliaError> {
-        let response = self
-            .client
-            .get(url)
-            .header("Accept", "application/json")
-            .send()
-            .await?
-            .json::<Value>()
-            .await?;
-        let data = response
-            .get(access_key)
-            .and_then(Value::as_array)
-            .ok_or(BancaDItaliaError::NoResult)?;
-        let result = serde_json::from_value(Value::Array(data.to_owned()))?;
-        Ok(result)
+        let payload = self.get_payload(url, ResponseFormat::Json).await?;
+        if self.deny_unknown_fields {
+            check_known_fields(&payload, access_key)?;
+        }
+        extract_data(&payload, access_key)
     }
 
     /// Retrieves currency data.
     ///
-    /// The function retrieves a registry of the currency. It stores them in a vector of `Currency` object. If the data
-    /// fetching fails it returns a `BancaDItaliaError`.
+    /// The function retrieves a registry of the currency. It stores them in a vector of `Currency` object, sorted by iso
+    /// code regardless of the order returned by the API. If the data fetching fails it returns a `BancaDItaliaError`.
     ///
     /// ## Returns
-    /// - `Ok(Vec<Currency>)`: A vector containing the listed currencies.
+    /// - `Ok(Vec<Currency>)`: A vector containing the listed currencies, sorted by iso code.
     /// - `Err(BancaDItaliaError)`: If data fetching fails.
     ///
     /// ## Example
@@ -163,17 +607,214 @@ impl BancaDItalia {
     /// }
     /// ```
     pub async fn get_currencies(&self) -> Result<Vec<Currency>, BancaDItaliaError> {
-        parse_currency(self.get_data(&currencies_url!(), "currencies").await?)
+        let mut currencies = self.get_currencies_unsorted().await?;
+        currencies.sort_by(|a, b| a.isocode.cmp(&b.isocode));
+        Ok(currencies)
+    }
+
+    /// Retrieves currency data in the requested format.
+    ///
+    /// The function requests the `currencies` endpoint with the `Accept` header of `format` and returns the body without
+    /// mapping it into `Currency`. The endpoint is served as JSON or CSV, as listed by `Endpoint::Currencies.info().formats`.
+    ///
+    /// ## Arguments
+    /// - `format`: The format of the response.
+    ///
+    /// ## Returns
+    /// - `Ok(FormattedPayload)`: The body, as JSON, CSV text or PDF bytes depending on `format`.
+    /// - `Err(BancaDItaliaError)`: If data fetching fails, the endpoint answers with an error status (e.g. for a format
+    ///   it does not serve) or the body does not match the format.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use bank_of_italy_api::{BancaDItalia, ResponseFormat};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let boi = BancaDItalia::new().unwrap();
+    ///     let currencies = boi.get_currencies_as(ResponseFormat::Json).await.unwrap();
+    ///     println!("{:#?}", currencies);
+    /// }
+    /// ```
+    pub async fn get_currencies_as(
+        &self,
+        format: ResponseFormat,
+    ) -> Result<FormattedPayload, BancaDItaliaError> {
+        self.get_formatted(&currencies_url!(self.base_url), format)
+            .await
+    }
+
+    /// Retrieves currency data in the order returned by the API.
+    ///
+    /// The function behaves like `get_currencies` but keeps the order in which Banca d'Italia returns the currencies.
+    ///
+    /// ## Returns
+    /// - `Ok(Vec<Currency>)`: A vector containing the listed currencies, in upstream order.
+    /// - `Err(BancaDItaliaError)`: If data fetching fails.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use bank_of_italy_api::BancaDItalia;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let boi = BancaDItalia::new().unwrap();
+    ///     let currencies = boi.get_currencies_unsorted().await.unwrap();
+    ///     println!("{:#?}", currencies);
+    /// }
+    /// ```
+    pub async fn get_currencies_unsorted(&self) -> Result<Vec<Currency>, BancaDItaliaError> {
+        parse_currency(
+            self.get_data(&currencies_url!(self.base_url), "currencies")
+                .await?,
+        )
+    }
+
+    /// Retrieves the currencies used in a country.
+    ///
+    /// The function fetches the currency registry and returns every currency whose `countries` contain an entry matching
+    /// `country` case-insensitively, either on the country iso code or, since some entries have no iso code, on the
+    /// country name.
+    ///
+    /// ## Arguments
+    /// - `country`: The iso code (e.g. `ITA`) or the name (e.g. `ITALY`) of the country.
+    ///
+    /// ## Returns
+    /// - `Ok(Vec<Currency>)`: The currencies used in the country, sorted by iso code.
+    /// - `Err(BancaDItaliaError)`: If data fetching fails.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use bank_of_italy_api::BancaDItalia;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let boi = BancaDItalia::new().unwrap();
+    ///     let currencies = boi.currencies_for_country("ITA").await.unwrap();
+    ///     println!("{:#?}", currencies);
+    /// }
+    /// ```
+    pub async fn currencies_for_country(
+        &self,
+        country: &str,
+    ) -> Result<Vec<Currency>, BancaDItaliaError> {
+        validate::non_empty("country", country)?;
+        let country = country.trim();
+        Ok(self
+            .get_currencies()
+            .await?
+            .into_iter()
+            .filter(|currency| {
+                currency.countries.iter().any(|c| {
+                    c.countryiso
+                        .as_deref()
+                        .is_some_and(|iso| iso.eq_ignore_ascii_case(country))
+                        || c.country.eq_ignore_ascii_case(country)
+                })
+            })
+            .collect())
+    }
+
+    /// Retrieves the currencies whose iso code starts with a prefix.
+    ///
+    /// The function fetches the currency registry and returns every currency whose iso code starts with `prefix`,
+    /// compared case-insensitively, e.g. for type-ahead currency pickers. Unlike the name lookups, only iso codes are
+    /// matched. An empty prefix returns the whole registry.
+    ///
+    /// ## Arguments
+    /// - `prefix`: The first letters of the iso code, e.g. `eu`.
+    ///
+    /// ## Returns
+    /// - `Ok(Vec<Currency>)`: The matching currencies, sorted by iso code.
+    /// - `Err(BancaDItaliaError)`: If data fetching fails.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use bank_of_italy_api::BancaDItalia;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let boi = BancaDItalia::new().unwrap();
+    ///     let currencies = boi.currencies_starting_with("eu").await.unwrap();
+    ///     println!("{:#?}", currencies);
+    /// }
+    /// ```
+    pub async fn currencies_starting_with(
+        &self,
+        prefix: &str,
+    ) -> Result<Vec<Currency>, BancaDItaliaError> {
+        let prefix = prefix.trim().to_uppercase();
+        Ok(self
+            .get_currencies()
+            .await?
+            .into_iter()
+            .filter(|currency| currency.isocode.to_uppercase().starts_with(&prefix))
+            .collect())
+    }
+
+    /// Resolves the display name of a currency.
+    ///
+    /// The currency registry and the latest rates do not always agree on how a currency is spelled, e.g. `Euro` and
+    /// `EURO`. The function prefers the registry `name`, falls back to the latest rate `currency` for codes missing from
+    /// the registry, and normalizes the result: surrounding and repeated whitespace is removed and all-caps names are
+    /// title-cased. Both endpoints are fetched on the first call; the names are then cached for every clone of the client.
+    /// A code missing from the cache triggers a single refetch, so currencies listed after the first call are resolved
+    /// too.
+    ///
+    /// ## Arguments
+    /// - `iso`: The iso code of the currency, matched case-insensitively.
+    ///
+    /// ## Returns
+    /// - `Ok(String)`: The canonical display name of the currency.
+    /// - `Err(BancaDItaliaError)`: If data fetching fails or the currency is listed by neither endpoint.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use bank_of_italy_api::BancaDItalia;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let boi = BancaDItalia::new().unwrap();
+    ///     let name = boi.resolve_currency_name("eur").await.unwrap();
+    ///     println!("{}", name);
+    /// }
+    /// ```
+    pub async fn resolve_currency_name(&self, iso: &str) -> Result<String, BancaDItaliaError> {
+        validate::non_empty("iso", iso)?;
+        let key = iso.trim().to_uppercase();
+        let names = match self.currency_names.load_full() {
+            Some(names) if names.contains_key(&key) => names,
+            _ => self.load_currency_names().await?,
+        };
+        names
+            .get(&key)
+            .cloned()
+            .ok_or_else(|| BancaDItaliaError::CurrencyNotFound(iso.to_string()))
+    }
+
+    /// Fetches both endpoints and replaces the display names cached for `resolve_currency_name`.
+    ///
+    /// ## Returns
+    /// - `Ok(Arc<HashMap<String, String>>)`: The display names, keyed by iso code.
+    /// - `Err(BancaDItaliaError)`: If data fetching fails.
+    async fn load_currency_names(&self) -> Result<Arc<HashMap<String, String>>, BancaDItaliaError> {
+        let (currencies, latest_rates) =
+            tokio::try_join!(self.get_currencies_unsorted(), self.get_latest_rate())?;
+        self.record_orphan_codes(&currencies, &latest_rates);
+        let names = Arc::new(currency_names(&currencies, &latest_rates));
+        self.currency_names.store(Some(Arc::clone(&names)));
+        Ok(names)
     }
 
     /// Retrieves the latest exchange rate data.
     ///
     /// The function retrieves the latest exchange rate data for current listed currencies. It stores them in a vector of `LatestRate` object.
-    /// If the data fetching fails it returns a `BancaDItaliaError`.
+    /// If the data fetching fails it returns a `BancaDItaliaError`. When the client is built with
+    /// `reject_rate_anomalies(true)`, implausible rates are reported as `BancaDItaliaError::RateAnomalies`.
     ///
     /// ## Returns
     /// - `Ok(Vec<LatestRate>)`: A vector containing the latest exchange rate for current liste currencies.
-    /// - `Err(BancaDItaliaError)`: If data fetching fails.
+    /// - `Err(BancaDItaliaError)`: If data fetching fails or, when enabled, the rates contain anomalies.
     ///
     /// ## Example
     /// ```rust
@@ -187,49 +828,2104 @@ impl BancaDItalia {
     /// }
     /// ```
     pub async fn get_latest_rate(&self) -> Result<Vec<LatestRate>, BancaDItaliaError> {
-        parse_latest_rates(self.get_data(&latestrate_url!(), "latestRates").await?)
+        let latest_rates = parse_latest_rates(
+            self.get_data(&latestrate_url!(self.base_url), "latestRates")
+                .await?,
+            self.strict_rates,
+            self.keep_raw,
+        )?;
+        self.check_rate_anomalies(&latest_rates)?;
+        Ok(latest_rates)
     }
-}
-
-/// Represents the information about data returned by the Banca d'Italia API.
-#[derive(Debug, Deserialize, Serialize)]
-pub struct ResultInfo {
-    /// The number of data point returned by the query.
-    #[serde(rename = "totalRecords")]
-    pub total_records: i32,
-    /// The timezone used in the data.
-    #[serde(rename = "timezoneReference")]
-    pub timezone_reference: String,
-    /// Any additional information about data.
-    pub notice: String,
-}
-
-/// Represents the metadata about the results returned by the API.
-#[derive(Debug, Deserialize, Serialize)]
-pub struct MetaData {
-    /// The results informations.
-    #[serde(rename = "resultsInfo")]
-    pub results_info: ResultInfo,
-}
 
-/// Represents the vector containing the currencies data.
-#[derive(Debug, Deserialize, Serialize)]
-pub struct Currencies {
-    /// The vector containing the currencies listed in the data.
-    pub currencies: Vec<Currency>,
-}
+    /// Retrieves the latest exchange rate data in the requested format.
+    ///
+    /// The function requests the `latestRates` endpoint with the `Accept` header of `format` and returns the body
+    /// without mapping it into `LatestRate`. The endpoint is served as JSON or CSV, as listed by
+    /// `Endpoint::LatestRates.info().formats`.
+    ///
+    /// ## Arguments
+    /// - `format`: The format of the response.
+    ///
+    /// ## Returns
+    /// - `Ok(FormattedPayload)`: The body, as JSON, CSV text or PDF bytes depending on `format`.
+    /// - `Err(BancaDItaliaError)`: If data fetching fails, the endpoint answers with an error status (e.g. for a format
+    ///   it does not serve) or the body does not match the format.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use bank_of_italy_api::{BancaDItalia, FormattedPayload, ResponseFormat};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let boi = BancaDItalia::new().unwrap();
+    ///     if let FormattedPayload::Csv(csv) = boi.get_latest_rate_as(ResponseFormat::Csv).await.unwrap() {
+    ///         println!("{}", csv);
+    ///     }
+    /// }
+    /// ```
+    pub async fn get_latest_rate_as(
+        &self,
+        format: ResponseFormat,
+    ) -> Result<FormattedPayload, BancaDItaliaError> {
+        self.get_formatted(&latestrate_url!(self.base_url), format)
+            .await
+    }
 
-#[derive(Debug, Deserialize, Serialize)]
-pub struct CurrencyAPI {
-    pub countries: Vec<CountryAPI>,
-    #[serde(rename = "isoCode")]
-    pub isocode: String,
+    /// Retrieves the latest exchange rate data, falling back to the last known rates on failure.
+    ///
+    /// The function behaves like `get_latest_rate` and wraps the rates in `Staleness::Fresh`. When the client is built
+    /// with `serve_stale_on_error(true)` and the fetch fails, it returns the snapshot of the last successful fetch (or
+    /// auto-refresh) wrapped in `Staleness::Stale`, so dashboards stay populated during brief outages. Without a prior
+    /// snapshot, or with the option disabled, the fetch error is returned.
+    ///
+    /// ## Returns
+    /// - `Ok(Staleness<Vec<LatestRate>>)`: The latest rates, flagged as fresh or stale.
+    /// - `Err(BancaDItaliaError)`: If data fetching fails and no stale rates can be served.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use bank_of_italy_api::BancaDItalia;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let boi = BancaDItalia::builder().serve_stale_on_error(true).build().unwrap();
+    ///     let latest_rates = boi.get_latest_rate_or_stale().await.unwrap();
+    ///     if latest_rates.is_stale() {
+    ///         println!("showing rates from {:?}", boi.last_successful_fetch());
+    ///     }
+    ///     println!("{:#?}", latest_rates.into_inner());
+    /// }
+    /// ```
+    pub async fn get_latest_rate_or_stale(
+        &self,
+    ) -> Result<Staleness<Vec<LatestRate>>, BancaDItaliaError> {
+        match self.get_latest_rate().await {
+            Ok(latest_rates) => {
+                if self.serve_stale_on_error {
+                    self.snapshot.store(Some(Arc::new(latest_rates.clone())));
+                }
+                Ok(Staleness::Fresh(latest_rates))
+            }
+            Err(err) => match self.current_snapshot() {
+                Some(snapshot) if self.serve_stale_on_error => {
+                    tracing::warn!(error = %err, "Serving stale latest rates");
+                    Ok(Staleness::Stale(snapshot.as_ref().clone()))
+                }
+                _ => Err(err),
+            },
+        }
+    }
+
+    /// Rejects implausible rates when the client is built with `reject_rate_anomalies(true)`.
+    ///
+    /// ## Arguments
+    /// - `latest_rates`: The parsed latest rates.
+    ///
+    /// ## Returns
+    /// - `Ok(())`: If the check is disabled or no anomaly is found.
+    /// - `Err(BancaDItaliaError)`: The anomalies found in the rates.
+    fn check_rate_anomalies(&self, latest_rates: &[LatestRate]) -> Result<(), BancaDItaliaError> {
+        let Some(max_rate) = self.max_plausible_rate else {
+            return Ok(());
+        };
+        let anomalies = Self::find_rate_anomalies(latest_rates, max_rate);
+        if anomalies.is_empty() {
+            Ok(())
+        } else {
+            Err(BancaDItaliaError::RateAnomalies(anomalies))
+        }
+    }
+
+    /// Compares the currency registry with the latest exchange rates.
+    ///
+    /// The function fetches the currency registry and the latest exchange rates concurrently and reports which iso codes
+    /// appear only in one of the two datasets. Note that the registry also lists historical currencies, which never appear
+    /// in the latest rates.
+    ///
+    /// ## Returns
+    /// - `Ok(Reconciliation)`: The comparison between the two datasets.
+    /// - `Err(BancaDItaliaError)`: If data fetching fails.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use bank_of_italy_api::BancaDItalia;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let boi = BancaDItalia::new().unwrap();
+    ///     let reconciliation = boi.reconcile().await.unwrap();
+    ///     println!("{:#?}", reconciliation.only_in_rates);
+    /// }
+    /// ```
+    pub async fn reconcile(&self) -> Result<Reconciliation, BancaDItaliaError> {
+        let (currencies, latest_rates) =
+            tokio::try_join!(self.get_currencies(), self.get_latest_rate())?;
+        self.record_orphan_codes(&currencies, &latest_rates);
+        Ok(reconcile_isocodes(&currencies, &latest_rates))
+    }
+
+    /// Lists the endpoints wrapped by the crate.
+    ///
+    /// The list is derived from `Endpoint::ALL`, so tools such as CLIs can build their commands from it and pick up new
+    /// endpoints automatically.
+    ///
+    /// ## Returns
+    /// - `&'static [EndpointInfo]`: The name, path and required query parameters of each endpoint.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use bank_of_italy_api::BancaDItalia;
+    ///
+    /// for endpoint in BancaDItalia::endpoints() {
+    ///     println!("{} -> {} {:?}", endpoint.name, endpoint.path, endpoint.required_params);
+    /// }
+    /// ```
+    pub fn endpoints() -> &'static [EndpointInfo] {
+        &ENDPOINTS
+    }
+
+    /// Returns the orphan iso codes collected so far.
+    ///
+    /// Orphan codes are listed in the latest rates but missing from the currency registry. They are only collected when
+    /// the client is built with `collect_orphan_codes(true)`, by the methods fetching both datasets, and are shared by
+    /// every clone of the client.
+    ///
+    /// ## Returns
+    /// - `Vec<String>`: The orphan iso codes seen since the client was built, sorted alphabetically; empty when
+    ///   collection is disabled.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use bank_of_italy_api::BancaDItalia;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let boi = BancaDItalia::builder().collect_orphan_codes(true).build().unwrap();
+    ///     boi.reconcile().await.unwrap();
+    ///     println!("{:?}", boi.orphan_codes());
+    /// }
+    /// ```
+    pub fn orphan_codes(&self) -> Vec<String> {
+        self.orphan_codes
+            .as_ref()
+            .map(|codes| {
+                codes
+                    .lock()
+                    .expect("the orphan codes lock is never poisoned")
+                    .iter()
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Records the orphan iso codes of a combined fetch when collection is enabled.
+    ///
+    /// ## Arguments
+    /// - `currencies`: The currency registry.
+    /// - `latest_rates`: The latest exchange rates.
+    fn record_orphan_codes(&self, currencies: &[Currency], latest_rates: &[LatestRate]) {
+        if let Some(codes) = &self.orphan_codes {
+            let orphans = reconcile_isocodes(currencies, latest_rates).only_in_rates;
+            codes
+                .lock()
+                .expect("the orphan codes lock is never poisoned")
+                .extend(orphans);
+        }
+    }
+
+    /// Checks that every endpoint wrapped by the crate still matches the live API.
+    ///
+    /// The function requests each endpoint concurrently, deserializes the response into the crate's API structs
+    /// (`CurrencyAPI`, `LatestRateAPI`) and maps them into `Currency` and `LatestRate` as the typed methods do,
+    /// recording whether the request succeeded, returned no data or failed to parse. It is a diagnostic to run after
+    /// Banca d'Italia changes something: every failure is reported in the returned `SelfTestReport`.
+    ///
+    /// ## Returns
+    /// - `SelfTestReport`: The outcome of each endpoint.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use bank_of_italy_api::BancaDItalia;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let boi = BancaDItalia::new().unwrap();
+    ///     let report = boi.self_test().await;
+    ///     println!("{:#?}", report);
+    /// }
+    /// ```
+    pub async fn self_test(&self) -> SelfTestReport {
+        let (currencies, latest_rates) = tokio::join!(
+            self.check_endpoint(&currencies_url!(self.base_url), "currencies", |data| {
+                parse_currency(data).map(|currencies| currencies.len())
+            }),
+            self.check_endpoint(&latestrate_url!(self.base_url), "latestRates", |data| {
+                parse_latest_rates(data, false, false).map(|rates| rates.len())
+            }),
+        );
+        SelfTestReport {
+            endpoints: vec![currencies, latest_rates],
+        }
+    }
+
+    /// Requests an endpoint and records the outcome of its deserialization and mapping.
+    ///
+    /// ## Arguments
+    /// - `url`: The url to data endpoint.
+    /// - `access_key`: The access key that allows to access data stored in JSON structure.
+    /// - `parse`: The function mapping the deserialized data into the crate's structs, returning the number of records.
+    ///
+    /// ## Returns
+    /// - `EndpointCheck`: The outcome of the request.
+    async fn check_endpoint<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        access_key: &str,
+        parse: impl FnOnce(Vec<T>) -> Result<usize, BancaDItaliaError>,
+    ) -> EndpointCheck {
+        let outcome = match self
+            .get_payload_with_status(url, ResponseFormat::Json)
+            .await
+        {
+            Err(err) => EndpointOutcome::RequestFailed(err.to_string()),
+            Ok((status, _)) if !(200..300).contains(&status) => {
+                EndpointOutcome::RequestFailed(format!("responded with status {}", status))
+            }
+            Ok((_, payload)) => match extract_data::<T>(&payload, access_key).and_then(parse) {
+                Ok(0) | Err(BancaDItaliaError::NoResult) => EndpointOutcome::Empty,
+                Ok(records) => EndpointOutcome::Ok { records },
+                Err(err) => EndpointOutcome::ParseFailed(err.to_string()),
+            },
+        };
+        EndpointCheck {
+            endpoint: endpoint_name(url).to_string(),
+            outcome,
+        }
+    }
+
+    /// Retrieves a quote between two currencies.
+    ///
+    /// The function fetches the latest exchange rates and computes the cross rate between `from` and `to` through their euro
+    /// rates. Banca d'Italia publishes reference (mid) rates only, so the returned rate acts as bid, mid and ask at the
+    /// same time.
+    ///
+    /// ## Arguments
+    /// - `from`: The iso code of the currency to convert from.
+    /// - `to`: The iso code of the currency to convert to.
+    ///
+    /// ## Returns
+    /// - `Ok(Quote)`: The units of `to` corresponding to one unit of `from`.
+    /// - `Err(BancaDItaliaError)`: If data fetching fails, a currency is not listed or its rate is unavailable.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use bank_of_italy_api::BancaDItalia;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let boi = BancaDItalia::new().unwrap();
+    ///     let quote = boi.quote("EUR", "USD").await.unwrap();
+    ///     println!("{:#?}", quote);
+    /// }
+    /// ```
+    pub async fn quote(&self, from: &str, to: &str) -> Result<Quote, BancaDItaliaError> {
+        validate::non_empty("from", from)?;
+        validate::non_empty("to", to)?;
+        let latest_rates = self.get_latest_rate().await?;
+        let (rate, reference_date) = cross_rate(&latest_rates, from, to)?;
+        Ok(Quote {
+            from: from.to_uppercase(),
+            to: to.to_uppercase(),
+            rate,
+            reference_date,
+            source: "Banca d'Italia".to_string(),
+        })
+    }
+
+    /// Converts an amount along an explicit path of currencies.
+    ///
+    /// The function fetches the latest exchange rates once and applies each hop of `path` in order, e.g.
+    /// `["GBP", "EUR", "USD", "JPY"]` converts from pounds to yen through the euro and the US dollar. Every hop uses the
+    /// same cross rate as `quote`, so the result only differs from a direct conversion by rounding; the path gives
+    /// explicit control over the routing.
+    ///
+    /// ## Arguments
+    /// - `amount`: The amount to convert, in the first currency of the path.
+    /// - `path`: The iso codes of the currencies to go through, at least two.
+    ///
+    /// ## Returns
+    /// - `Ok(Decimal)`: The amount in the last currency of the path.
+    /// - `Err(BancaDItaliaError)`: If data fetching fails, the path is too short or a hop fails, in which case
+    ///   `BancaDItaliaError::ConversionHop` reports the hop and its cause, e.g. an unavailable rate or an amount
+    ///   overflowing `Decimal`.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use bank_of_italy_api::BancaDItalia;
+    /// use rust_decimal::Decimal;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let boi = BancaDItalia::new().unwrap();
+    ///     let yen = boi
+    ///         .convert_via(Decimal::from(100), &["GBP", "EUR", "USD", "JPY"])
+    ///         .await
+    ///         .unwrap();
+    ///     println!("{}", yen);
+    /// }
+    /// ```
+    pub async fn convert_via(
+        &self,
+        amount: Decimal,
+        path: &[&str],
+    ) -> Result<Decimal, BancaDItaliaError> {
+        if path.len() < 2 {
+            return Err(BancaDItaliaError::InvalidArgument {
+                name: "path".to_string(),
+                reason: "must contain at least two currencies".to_string(),
+            });
+        }
+        for iso in path {
+            validate::non_empty("path", iso)?;
+        }
+        let latest_rates = self.get_latest_rate().await?;
+        path.windows(2)
+            .enumerate()
+            .try_fold(amount, |amount, (hop, pair)| {
+                cross_rate(&latest_rates, pair[0], pair[1])
+                    .and_then(|(rate, _)| {
+                        amount
+                            .checked_mul(rate)
+                            .ok_or_else(|| BancaDItaliaError::AmountOverflow {
+                                iso: pair[0].to_uppercase(),
+                            })
+                    })
+                    .map_err(|err| BancaDItaliaError::ConversionHop {
+                        hop: hop + 1,
+                        from: pair[0].to_uppercase(),
+                        to: pair[1].to_uppercase(),
+                        source: Box::new(err),
+                    })
+            })
+    }
+
+    /// Retrieves the latest rate of a currency against several bases at once.
+    ///
+    /// Every rate is the units of `iso` for one unit of the base, the direction of `eur_rate`. The `EUR` base reads
+    /// `eur_rate` directly; with the `usd` feature, the `USD` base reads `usd_rate` normalized by its exchange convention;
+    /// any other base is cross-computed through the euro rates as `iso.eur_rate / base.eur_rate`. Bases whose rate is
+    /// unavailable are skipped.
+    ///
+    /// ## Arguments
+    /// - `iso`: The iso code of the currency to quote.
+    /// - `bases`: The iso codes of the bases to quote it against.
+    ///
+    /// ## Returns
+    /// - `Ok(HashMap<String, Decimal>)`: The units of `iso` for one unit of each base, keyed by upper-case base.
+    /// - `Err(BancaDItaliaError)`: If data fetching fails, or `iso` or a base is not listed in the latest rates.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use bank_of_italy_api::BancaDItalia;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let boi = BancaDItalia::new().unwrap();
+    ///     let gbp = boi.multi_base("GBP", &["EUR", "USD"]).await.unwrap();
+    ///     println!("{:#?}", gbp);
+    /// }
+    /// ```
+    pub async fn multi_base(
+        &self,
+        iso: &str,
+        bases: &[&str],
+    ) -> Result<HashMap<String, Decimal>, BancaDItaliaError> {
+        validate::non_empty("iso", iso)?;
+        for base in bases {
+            validate::non_empty("bases", base)?;
+        }
+        let latest_rates = self.get_latest_rate().await?;
+        find_rate(&latest_rates, iso)?;
+        let mut rates = HashMap::new();
+        for base in bases {
+            match base_rate(&latest_rates, base, iso) {
+                Ok(rate) => {
+                    rates.insert(base.to_uppercase(), rate);
+                }
+                Err(BancaDItaliaError::RateUnavailable { .. }) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(rates)
+    }
+
+    /// Values a portfolio of holdings in a single currency.
+    ///
+    /// The function fetches the latest exchange rates once, converts every holding into `target` with the same cross
+    /// rate as `quote` and sums the results.
+    ///
+    /// ## Arguments
+    /// - `holdings`: The amounts held, each with the iso code of its currency.
+    /// - `target`: The iso code of the currency to value the portfolio in.
+    ///
+    /// ## Returns
+    /// - `Ok(Decimal)`: The total value of the holdings in `target`, zero for an empty portfolio.
+    /// - `Err(BancaDItaliaError)`: If data fetching fails, a currency is not listed or has an unavailable rate, or the
+    ///   value of a holding overflows `Decimal` (`AmountOverflow`), in which case the error names the currency.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use bank_of_italy_api::BancaDItalia;
+    /// use rust_decimal::Decimal;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let boi = BancaDItalia::new().unwrap();
+    ///     let holdings = [(Decimal::from(1000), "USD"), (Decimal::from(500), "GBP")];
+    ///     let total = boi.value_portfolio(&holdings, "EUR").await.unwrap();
+    ///     println!("{}", total);
+    /// }
+    /// ```
+    pub async fn value_portfolio(
+        &self,
+        holdings: &[(Decimal, &str)],
+        target: &str,
+    ) -> Result<Decimal, BancaDItaliaError> {
+        validate::non_empty("target", target)?;
+        for (_, iso) in holdings {
+            validate::non_empty("holdings", iso)?;
+        }
+        let latest_rates = self.get_latest_rate().await?;
+        holdings
+            .iter()
+            .try_fold(Decimal::ZERO, |total, (amount, iso)| {
+                let (rate, _) = cross_rate(&latest_rates, iso, target)?;
+                amount
+                    .checked_mul(rate)
+                    .and_then(|value| total.checked_add(value))
+                    .ok_or_else(|| BancaDItaliaError::AmountOverflow {
+                        iso: iso.to_uppercase(),
+                    })
+            })
+    }
+
+    /// Retrieves the latest rate of a currency from its name.
+    ///
+    /// The function matches `name` case-insensitively against the `currency` field of the latest rates, e.g. `pound
+    /// sterling`. When no name matches exactly, it falls back to partial matches, where either name contains the other,
+    /// so `Japanese Yen` finds `Yen`. The iso code lookups remain the canonical path; this one is meant for interactive
+    /// tools.
+    ///
+    /// ## Arguments
+    /// - `name`: The name of the currency.
+    ///
+    /// ## Returns
+    /// - `Ok(LatestRate)`: The latest rate of the only matching currency.
+    /// - `Err(BancaDItaliaError)`: If data fetching fails, no currency matches, or several do, in which case
+    ///   `BancaDItaliaError::AmbiguousCurrency` lists the candidates.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use bank_of_italy_api::BancaDItalia;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let boi = BancaDItalia::new().unwrap();
+    ///     let rate = boi.get_latest_rate_by_name("Japanese Yen").await.unwrap();
+    ///     println!("{}: {}", rate.isocode, rate.eur_rate);
+    /// }
+    /// ```
+    pub async fn get_latest_rate_by_name(
+        &self,
+        name: &str,
+    ) -> Result<LatestRate, BancaDItaliaError> {
+        validate::non_empty("name", name)?;
+        find_rate_by_name(self.get_latest_rate().await?, name)
+    }
+
+    /// Retrieves the latest euro price of one unit of every currency.
+    ///
+    /// The function fetches the latest exchange rates and returns `LatestRate::eur_per_unit` for each currency, the
+    /// reciprocal of `eur_rate` rounded to 10 decimal places: `eur_rate` is the units of currency for one euro, while this
+    /// table holds the euros for one unit of currency (e.g. `GBP -> 1.1527` means one pound costs 1.1527 euros).
+    /// Currencies with an unavailable rate are skipped, and `EUR` always maps to `1`.
+    ///
+    /// ## Returns
+    /// - `Ok(HashMap<String, Decimal>)`: The euros for one unit of currency, keyed by iso code.
+    /// - `Err(BancaDItaliaError)`: If data fetching fails.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use bank_of_italy_api::BancaDItalia;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let boi = BancaDItalia::new().unwrap();
+    ///     let eur_quotes = boi.eur_quote_table().await.unwrap();
+    ///     println!("{:#?}", eur_quotes.get("GBP"));
+    /// }
+    /// ```
+    pub async fn eur_quote_table(&self) -> Result<HashMap<String, Decimal>, BancaDItaliaError> {
+        let mut table: HashMap<String, Decimal> = self
+            .get_latest_rate()
+            .await?
+            .into_iter()
+            .filter_map(|rate| {
+                rate.eur_per_unit()
+                    .map(|eur_quote| (rate.isocode, eur_quote))
+            })
+            .collect();
+        table.insert("EUR".to_string(), Decimal::ONE);
+        Ok(table)
+    }
+
+    /// Ranks the currencies of the latest rates by their strength against the euro.
+    ///
+    /// Strength here means how many euros one unit of currency buys, i.e. the reciprocal of `eur_rate` as returned by
+    /// `eur_quote_table`. Currencies are sorted from the strongest to the weakest; ties are broken by iso code so the
+    /// order is deterministic. Currencies with an unavailable rate are skipped, and `EUR` is ranked with a value of `1`.
+    ///
+    /// ## Returns
+    /// - `Ok(Vec<(String, Decimal)>)`: The iso codes paired with the euros for one unit of currency, strongest first.
+    /// - `Err(BancaDItaliaError)`: If data fetching fails.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use bank_of_italy_api::BancaDItalia;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let boi = BancaDItalia::new().unwrap();
+    ///     let ranking = boi.rank_by_eur_strength().await.unwrap();
+    ///     println!("strongest: {:?}", ranking.first());
+    ///     println!("weakest: {:?}", ranking.last());
+    /// }
+    /// ```
+    pub async fn rank_by_eur_strength(&self) -> Result<Vec<(String, Decimal)>, BancaDItaliaError> {
+        let mut ranking: Vec<(String, Decimal)> =
+            self.eur_quote_table().await?.into_iter().collect();
+        ranking.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        Ok(ranking)
+    }
+
+    /// Retrieves the latest exchange rates against the US dollar.
+    ///
+    /// The function fetches the latest exchange rates and returns, for every currency, the units of currency corresponding
+    /// to one US dollar. Rates quoted as US dollars per unit of currency (convention code `I`) are inverted so that every
+    /// value follows the same direction. Currencies with an unavailable rate or an unknown convention are skipped. Requires
+    /// the `usd` feature.
+    ///
+    /// ## Returns
+    /// - `Ok(HashMap<String, Decimal>)`: The units of currency for one US dollar, keyed by iso code.
+    /// - `Err(BancaDItaliaError)`: If data fetching fails.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use bank_of_italy_api::BancaDItalia;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let boi = BancaDItalia::new().unwrap();
+    ///     let usd_rates = boi.get_latest_rate_usd_based().await.unwrap();
+    ///     println!("{:#?}", usd_rates.get("EUR"));
+    /// }
+    /// ```
+    #[cfg(feature = "usd")]
+    pub async fn get_latest_rate_usd_based(
+        &self,
+    ) -> Result<HashMap<String, Decimal>, BancaDItaliaError> {
+        Ok(self
+            .get_latest_rate()
+            .await?
+            .into_iter()
+            .filter_map(|rate| {
+                rate.units_per_usd()
+                    .map(|usd_rate| (rate.isocode, usd_rate))
+            })
+            .collect())
+    }
+
+    /// Measures how consistent the euro and US dollar legs of the latest rates are for a currency pair.
+    ///
+    /// The function computes the cross rate from `from` to `to` twice, once through the euro rates and once through the
+    /// US dollar rates (normalized by their exchange convention), and returns the relative difference
+    /// `|eur_cross - usd_cross| / eur_cross`. Both derivations should match up to rounding, so a large value signals stale
+    /// or inconsistent upstream data. Requires the `usd` feature.
+    ///
+    /// ## Arguments
+    /// - `from`: The iso code of the currency to convert from.
+    /// - `to`: The iso code of the currency to convert to.
+    ///
+    /// ## Returns
+    /// - `Ok(Decimal)`: The relative difference between the two cross rates, e.g. `0.0001` for 0.01%.
+    /// - `Err(BancaDItaliaError)`: If data fetching fails, a currency is not listed or any of its legs is unavailable.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use bank_of_italy_api::BancaDItalia;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let boi = BancaDItalia::new().unwrap();
+    ///     let spread = boi.cross_rate_consistency("GBP", "JPY").await.unwrap();
+    ///     println!("{}", spread);
+    /// }
+    /// ```
+    #[cfg(feature = "usd")]
+    pub async fn cross_rate_consistency(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<Decimal, BancaDItaliaError> {
+        validate::non_empty("from", from)?;
+        validate::non_empty("to", to)?;
+        let latest_rates = self.get_latest_rate().await?;
+        let (eur_cross, _) = cross_rate(&latest_rates, from, to)?;
+        let usd_cross = usd_cross_rate(&latest_rates, from, to)?;
+        Ok(((eur_cross - usd_cross) / eur_cross).abs())
+    }
+
+    /// Computes the EUR/USD rate implied by the two legs of every currency in the latest rates.
+    ///
+    /// For each currency the function divides `eur_rate` (units per euro) by `usd_rate` normalized to units per US dollar,
+    /// which yields US dollars per euro. Since both legs are published together, the values should cluster tightly around
+    /// the actual EUR/USD rate, differing only by rounding; an outlier points to a stale or inconsistent leg. Currencies
+    /// with an unavailable leg or an unknown convention are skipped. Requires the `usd` feature.
+    ///
+    /// ## Returns
+    /// - `Ok(HashMap<String, Decimal>)`: The implied US dollars per euro, keyed by iso code.
+    /// - `Err(BancaDItaliaError)`: If data fetching fails.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use bank_of_italy_api::BancaDItalia;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let boi = BancaDItalia::new().unwrap();
+    ///     let implied = boi.implied_eur_usd().await.unwrap();
+    ///     println!("{:#?}", implied);
+    /// }
+    /// ```
+    #[cfg(feature = "usd")]
+    pub async fn implied_eur_usd(&self) -> Result<HashMap<String, Decimal>, BancaDItaliaError> {
+        Ok(self
+            .get_latest_rate()
+            .await?
+            .into_iter()
+            .filter_map(|rate| {
+                let implied = rate.eur_rate.checked_div(rate.units_per_usd()?)?;
+                (!implied.is_zero()).then_some((rate.isocode, implied))
+            })
+            .collect())
+    }
+
+    /// Retrieves the distinct usd exchange conventions found in the latest rates.
+    ///
+    /// The function is useful to spot conventions that `UsdExchangeConvention` does not map yet. Requires the `usd`
+    /// feature.
+    ///
+    /// ## Returns
+    /// - `Ok(Vec<String>)`: The distinct `usd_exchange_convention` values, sorted alphabetically.
+    /// - `Err(BancaDItaliaError)`: If data fetching fails.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use bank_of_italy_api::BancaDItalia;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let boi = BancaDItalia::new().unwrap();
+    ///     let conventions = boi.distinct_usd_conventions().await.unwrap();
+    ///     println!("{:#?}", conventions);
+    /// }
+    /// ```
+    #[cfg(feature = "usd")]
+    pub async fn distinct_usd_conventions(&self) -> Result<Vec<String>, BancaDItaliaError> {
+        Ok(self
+            .get_latest_rate()
+            .await?
+            .into_iter()
+            .map(|rate| rate.usd_exchange_convention)
+            .collect::<BTreeSet<String>>()
+            .into_iter()
+            .collect())
+    }
+
+    /// Starts a background task that periodically refreshes the latest rates snapshot.
+    ///
+    /// The task fetches the latest exchange rates every `interval` and atomically swaps them into the snapshot returned by
+    /// `current_snapshot`. Failed refreshes are logged with a `tracing` warning and leave the previous snapshot in place.
+    /// The function must be called from within a tokio runtime.
+    ///
+    /// ## Arguments
+    /// - `interval`: The time between two refreshes. The first refresh happens immediately.
+    ///
+    /// ## Returns
+    /// - `RefreshHandle`: The handle used to stop the background task. Dropping the handle also stops the task.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use bank_of_italy_api::BancaDItalia;
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let boi = BancaDItalia::new().unwrap();
+    ///     let handle = boi.spawn_auto_refresh(Duration::from_secs(3600));
+    ///     println!("{:#?}", boi.current_snapshot());
+    ///     handle.stop();
+    /// }
+    /// ```
+    pub fn spawn_auto_refresh(&self, interval: Duration) -> RefreshHandle {
+        let boi = self.clone();
+        let task = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                match boi.get_latest_rate().await {
+                    Ok(latest_rates) => boi.snapshot.store(Some(Arc::new(latest_rates))),
+                    Err(err) => tracing::warn!(error = %err, "auto-refresh of latest rates failed"),
+                }
+            }
+        });
+        RefreshHandle { task }
+    }
+
+    /// Starts a background task that refreshes the latest rates snapshot following the publication schedule.
+    ///
+    /// Banca d'Italia publishes the latest rates once per business day, in the afternoon Central European Time. Instead
+    /// of polling at a fixed interval, the task polls every `PublicationSchedule::interval` only within the publication
+    /// window of a business day until a rate with that day's `reference_date` shows up, then sleeps until the window of
+    /// the next business day. Holidays are not known to the crate: on a business day without publication, the task
+    /// polls until the end of the window and then moves on. The schedule is set with
+    /// `BancaDItaliaBuilder::publication_schedule`. The function must be called from within a tokio runtime.
+    ///
+    /// ## Returns
+    /// - `RefreshHandle`: The handle used to stop the background task. Dropping the handle also stops the task.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use bank_of_italy_api::BancaDItalia;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let boi = BancaDItalia::new().unwrap();
+    ///     let handle = boi.poll_latest_rate_smart();
+    ///     println!("{:#?}", boi.current_snapshot());
+    ///     handle.stop();
+    /// }
+    /// ```
+    pub fn poll_latest_rate_smart(&self) -> RefreshHandle {
+        let boi = self.clone();
+        let task = tokio::spawn(async move {
+            loop {
+                match boi.get_latest_rate().await {
+                    Ok(latest_rates) => boi.snapshot.store(Some(Arc::new(latest_rates))),
+                    Err(err) => {
+                        tracing::warn!(error = %err, "smart refresh of latest rates failed")
+                    }
+                }
+                let latest = boi
+                    .snapshot
+                    .load()
+                    .as_ref()
+                    .and_then(|rates| rates.iter().map(|rate| rate.reference_date).max());
+                let delay = next_poll_delay(boi.clock.now(), latest, &boi.publication_schedule);
+                tokio::time::sleep(delay).await;
+            }
+        });
+        RefreshHandle { task }
+    }
+
+    /// Returns the latest rates snapshot maintained by the auto-refresh task.
+    ///
+    /// The function never blocks on the network: it returns the snapshot stored by the last successful refresh.
+    ///
+    /// ## Returns
+    /// - `Some(Arc<Vec<LatestRate>>)`: The latest rates stored by the last successful refresh.
+    /// - `None`: If no refresh has succeeded yet.
+    pub fn current_snapshot(&self) -> Option<Arc<Vec<LatestRate>>> {
+        self.snapshot.load_full()
+    }
+
+    /// Returns when data was last retrieved successfully from Banca d'Italia.
+    ///
+    /// The timestamp is updated by every request answered with a success status, including the ones sent by the
+    /// auto-refresh task, and is shared by every clone of the client. It can feed health checks and staleness alarms.
+    ///
+    /// ## Returns
+    /// - `Some(OffsetDateTime)`: The UTC time at which the last successful response was received.
+    /// - `None`: If no request has succeeded yet.
+    pub fn last_successful_fetch(&self) -> Option<OffsetDateTime> {
+        self.last_success.load_full().map(|time| *time)
+    }
+
+    /// Checks whether a currency is currently quoted by Banca d'Italia.
+    ///
+    /// A currency is tradable when it is listed in the latest rates with an available euro rate. The function reuses the
+    /// snapshot maintained by `spawn_auto_refresh` when there is one, so repeated checks do not hit the network, and
+    /// fetches the latest rates otherwise.
+    ///
+    /// ## Arguments
+    /// - `iso`: The iso code of the currency, matched case-insensitively.
+    ///
+    /// ## Returns
+    /// - `Ok(bool)`: Whether the currency has an available rate.
+    /// - `Err(BancaDItaliaError)`: If data fetching fails.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use bank_of_italy_api::BancaDItalia;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let boi = BancaDItalia::new().unwrap();
+    ///     assert!(boi.is_tradable("usd").await.unwrap());
+    /// }
+    /// ```
+    pub async fn is_tradable(&self, iso: &str) -> Result<bool, BancaDItaliaError> {
+        validate::non_empty("iso", iso)?;
+        let latest_rates = match self.current_snapshot() {
+            Some(snapshot) => snapshot,
+            None => Arc::new(self.get_latest_rate().await?),
+        };
+        Ok(find_rate(&latest_rates, iso).is_ok_and(|rate| !rate.eur_rate.is_zero()))
+    }
+
+    /// Fetches the latest exchange rates and writes them to a local file.
+    ///
+    /// The function fetches the latest exchange rates together with the response metadata and writes them as a pretty
+    /// printed `RatesReport` JSON document. The report is first written to a temporary file next to `path` and then renamed,
+    /// so readers never observe a partially written file. The temporary file is removed if writing or renaming it fails.
+    ///
+    /// ## Arguments
+    /// - `path`: The path of the file to write.
+    ///
+    /// ## Returns
+    /// - `Ok(())`: If the snapshot has been written.
+    /// - `Err(BancaDItaliaError)`: If data fetching or writing the file fails.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use bank_of_italy_api::BancaDItalia;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let boi = BancaDItalia::new().unwrap();
+    ///     boi.snapshot_to_file("latest_rates_snapshot.json").await.unwrap();
+    /// }
+    /// ```
+    pub async fn snapshot_to_file(&self, path: impl AsRef<Path>) -> Result<(), BancaDItaliaError> {
+        let payload = self
+            .get_payload(&latestrate_url!(self.base_url), ResponseFormat::Json)
+            .await?;
+        if self.deny_unknown_fields {
+            check_known_fields(&payload, "latestRates")?;
+        }
+        let report = RatesReport {
+            results_info: serde_json::from_slice::<MetaData>(&payload)?.results_info,
+            latest_rates: parse_latest_rates(
+                extract_data(&payload, "latestRates")?,
+                self.strict_rates,
+                self.keep_raw,
+            )?,
+        };
+        self.check_rate_anomalies(&report.latest_rates)?;
+
+        let path = path.as_ref();
+        let mut tmp_path = path.as_os_str().to_owned();
+        tmp_path.push(".tmp");
+        let contents = serde_json::to_vec_pretty(&report)?;
+        if let Err(err) =
+            std::fs::write(&tmp_path, contents).and_then(|()| std::fs::rename(&tmp_path, path))
+        {
+            let _ = std::fs::remove_file(&tmp_path);
+            return Err(err.into());
+        }
+        Ok(())
+    }
+
+    /// Reads the latest exchange rate data from a local file.
+    ///
+    /// The function reads a previously saved response of the `/latestRates` endpoint and runs it through the same parsing
+    /// pipeline used by `get_latest_rate`. It is useful for offline analysis or batch jobs over archived responses.
+    ///
+    /// ## Arguments
+    /// - `path`: The path to the JSON file containing the saved response.
+    ///
+    /// ## Returns
+    /// - `Ok(Vec<LatestRate>)`: A vector containing the exchange rates stored in the file.
+    /// - `Err(BancaDItaliaError)`: If reading or parsing the file fails.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use bank_of_italy_api::BancaDItalia;
+    ///
+    /// let latest_rates = BancaDItalia::latest_rate_from_file("latest_rates.json").unwrap();
+    /// println!("{:#?}", latest_rates);
+    /// ```
+    pub fn latest_rate_from_file(
+        path: impl AsRef<Path>,
+    ) -> Result<Vec<LatestRate>, BancaDItaliaError> {
+        parse_latest_rates(
+            extract_data(&std::fs::read(path)?, "latestRates")?,
+            false,
+            false,
+        )
+    }
+
+    /// Reads the number of records announced by a raw Banca d'Italia response.
+    ///
+    /// The function deserializes only the `resultsInfo` metadata of the response and skips over the data array without
+    /// allocating it, so callers can short-circuit on empty responses or preallocate before running the full parse.
+    ///
+    /// ## Arguments
+    /// - `payload`: The raw JSON response, e.g. the content of a saved response file.
+    ///
+    /// ## Returns
+    /// - `Ok(i32)`: The value of `resultsInfo.totalRecords`.
+    /// - `Err(BancaDItaliaError)`: If the metadata is missing or cannot be deserialized.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use bank_of_italy_api::BancaDItalia;
+    ///
+    /// let payload = std::fs::read("latest_rates.json").unwrap();
+    /// if BancaDItalia::total_records(&payload).unwrap() > 0 {
+    ///     let latest_rates = BancaDItalia::latest_rate_from_file("latest_rates.json").unwrap();
+    ///     println!("{:#?}", latest_rates);
+    /// }
+    /// ```
+    pub fn total_records(payload: &[u8]) -> Result<i32, BancaDItaliaError> {
+        Ok(serde_json::from_slice::<MetaData>(payload)?
+            .results_info
+            .total_records)
+    }
+
+    /// Finds the latest rates that changed since a previous snapshot.
+    ///
+    /// A rate has changed when its `eur_rate`, `usd_rate` (with the `usd` feature) or `reference_date` differs from the
+    /// rate with the same iso code in `previous`, or when its iso code is not in `previous` at all. Sync jobs can combine
+    /// it with `snapshot_to_file` to write only the deltas.
+    ///
+    /// ## Arguments
+    /// - `current`: The latest rates just fetched.
+    /// - `previous`: The latest rates stored by the previous sync.
+    ///
+    /// ## Returns
+    /// - `Vec<LatestRate>`: The changed rates, in the order of `current`.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use bank_of_italy_api::BancaDItalia;
+    ///
+    /// let previous = BancaDItalia::latest_rate_from_file("previous.json").unwrap();
+    /// let current = BancaDItalia::latest_rate_from_file("latest_rates.json").unwrap();
+    /// for rate in BancaDItalia::changed_since(&current, &previous) {
+    ///     println!("{}: {}", rate.isocode, rate.eur_rate);
+    /// }
+    /// ```
+    pub fn changed_since(current: &[LatestRate], previous: &[LatestRate]) -> Vec<LatestRate> {
+        let previous: HashMap<&str, &LatestRate> = previous
+            .iter()
+            .map(|rate| (rate.isocode.as_str(), rate))
+            .collect();
+        current
+            .iter()
+            .filter(|rate| {
+                previous.get(rate.isocode.as_str()).is_none_or(|old| {
+                    let changed =
+                        old.eur_rate != rate.eur_rate || old.reference_date != rate.reference_date;
+                    #[cfg(feature = "usd")]
+                    let changed = changed || old.usd_rate != rate.usd_rate;
+                    changed
+                })
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Finds the currencies added to or removed from the registry between two fetches.
+    ///
+    /// ## Arguments
+    /// - `old`: The currency registry fetched previously.
+    /// - `new`: The currency registry just fetched.
+    ///
+    /// ## Returns
+    /// - `RegistryDiff`: The iso codes only in `new` (added) and only in `old` (removed), sorted.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use bank_of_italy_api::BancaDItalia;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let boi = BancaDItalia::new().unwrap();
+    ///     let old = boi.get_currencies().await.unwrap();
+    ///     let new = boi.get_currencies().await.unwrap();
+    ///     assert!(BancaDItalia::registry_diff(&old, &new).is_empty());
+    /// }
+    /// ```
+    pub fn registry_diff(old: &[Currency], new: &[Currency]) -> RegistryDiff {
+        let old: BTreeSet<&str> = old.iter().map(|c| c.isocode.as_str()).collect();
+        let new: BTreeSet<&str> = new.iter().map(|c| c.isocode.as_str()).collect();
+        RegistryDiff {
+            added: new.difference(&old).map(|s| s.to_string()).collect(),
+            removed: old.difference(&new).map(|s| s.to_string()).collect(),
+        }
+    }
+
+    /// Flattens the currency registry into one row per currency and country.
+    ///
+    /// The function denormalizes the nested currency/country structure into rows ready to be loaded into a relational
+    /// table. Dates are kept as `time::Date`, so the caller controls their SQL formatting. Currencies without any country
+    /// produce no row.
+    ///
+    /// ## Arguments
+    /// - `currencies`: The currency registry.
+    ///
+    /// ## Returns
+    /// - `Vec<RegistryRow>`: One row per (currency, country) pair, in registry order.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use bank_of_italy_api::BancaDItalia;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let boi = BancaDItalia::new().unwrap();
+    ///     let currencies = boi.get_currencies().await.unwrap();
+    ///     for row in BancaDItalia::registry_rows(&currencies) {
+    ///         println!("{} {} {}", row.isocode, row.country, row.validity_start_date);
+    ///     }
+    /// }
+    /// ```
+    pub fn registry_rows(currencies: &[Currency]) -> Vec<RegistryRow> {
+        currencies
+            .iter()
+            .flat_map(|currency| {
+                currency.countries.iter().map(|country| RegistryRow {
+                    isocode: currency.isocode.clone(),
+                    currency_name: currency.name.clone(),
+                    country: country.country.clone(),
+                    country_iso: country.countryiso.clone(),
+                    validity_start_date: country.validity_start_date,
+                    validity_end_date: country.validity_end_date,
+                })
+            })
+            .collect()
+    }
+
+    /// Finds the implausible rates in a set of latest rates.
+    ///
+    /// Exchange reference rates are expected to be positive and within plausible bounds, so every `eur_rate` or `usd_rate`
+    /// that is negative or above `max_rate` is reported. Unavailable rates (`N.A.`, parsed as zero) are not anomalies.
+    ///
+    /// ## Arguments
+    /// - `latest_rates`: The latest rates to check.
+    /// - `max_rate`: The largest plausible rate, e.g. `DEFAULT_MAX_PLAUSIBLE_RATE`.
+    ///
+    /// ## Returns
+    /// - `Vec<RateAnomaly>`: The anomalies found, in the order of the input rates.
+    pub fn find_rate_anomalies(latest_rates: &[LatestRate], max_rate: Decimal) -> Vec<RateAnomaly> {
+        latest_rates
+            .iter()
+            .flat_map(|rate| {
+                #[cfg_attr(not(feature = "usd"), allow(unused_mut))]
+                let mut fields = vec![("eurRate", rate.eur_rate)];
+                #[cfg(feature = "usd")]
+                fields.push(("usdRate", rate.usd_rate));
+                fields.into_iter().filter_map(move |(field, value)| {
+                    let kind = if value.is_sign_negative() && !value.is_zero() {
+                        RateAnomalyKind::Negative
+                    } else if value > max_rate {
+                        RateAnomalyKind::AboveThreshold
+                    } else {
+                        return None;
+                    };
+                    Some(RateAnomaly {
+                        isocode: rate.isocode.clone(),
+                        field: field.to_string(),
+                        value,
+                        kind,
+                    })
+                })
+            })
+            .collect()
+    }
+}
+
+/// A builder to configure a `BancaDItalia` client.
+///
+/// Every option left unset keeps the `reqwest` default: idle connections are kept for 90 seconds, there is no limit on
+/// idle connections per host and HTTP/2 is only used when negotiated. For a polling workload, a `pool_idle_timeout`
+/// longer than the polling interval and a `pool_max_idle_per_host` of 1 keep a single warm connection to Banca d'Italia.
+/// Rate anomaly detection is disabled by default.
+///
+/// The base url is resolved with the following precedence: the url set with `base_url`, then the `BOI_BASE_URL`
+/// environment variable, then the Banca d'Italia API.
+#[derive(Default)]
+pub struct BancaDItaliaBuilder {
+    /// The base url of the API, if different from the Banca d'Italia one.
+    base_url: Option<String>,
+    /// The base urls tried in order when the base url cannot be reached.
+    fallback_base_urls: Vec<String>,
+    /// How long idle connections are kept in the pool.
+    pool_idle_timeout: Option<Duration>,
+    /// The maximum number of idle connections kept per host.
+    pool_max_idle_per_host: Option<usize>,
+    /// Whether HTTP/2 is used without negotiation.
+    http2_prior_knowledge: bool,
+    /// Whether redirects are followed, if different from the `reqwest` default.
+    follow_redirects: Option<bool>,
+    /// The maximum number of redirects followed, if different from the `reqwest` default.
+    max_redirects: Option<usize>,
+    /// Whether implausible rates are rejected.
+    reject_rate_anomalies: bool,
+    /// The largest plausible rate, if different from `DEFAULT_MAX_PLAUSIBLE_RATE`.
+    max_plausible_rate: Option<Decimal>,
+    /// Whether unavailable rates are reported as errors.
+    strict_rates: bool,
+    /// Whether the rates keep their original string representation.
+    keep_raw: bool,
+    /// Whether fields not consumed by the crate are reported as errors.
+    deny_unknown_fields: bool,
+    /// Whether the last known latest rates are served when a fetch fails.
+    serve_stale_on_error: bool,
+    /// Whether orphan iso codes are collected during combined fetches.
+    collect_orphan_codes: bool,
+    /// The hook invoked after each request.
+    on_request: Option<Arc<RequestHook>>,
+    /// The maximum number of requests in flight, if different from `DEFAULT_MAX_CONCURRENCY`.
+    max_concurrency: Option<usize>,
+    /// The publication schedule, if different from the default one.
+    publication_schedule: Option<PublicationSchedule>,
+    /// The source of the current time, if different from the system clock.
+    clock: Option<Arc<dyn Clock>>,
+}
+
+impl BancaDItaliaBuilder {
+    /// Sets the base url of the API, overriding the `BOI_BASE_URL` environment variable.
+    ///
+    /// ## Arguments
+    /// - `base_url`: The base url of the API, e.g. the url of a mock server.
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Sets a chain of base urls tried in order.
+    ///
+    /// The first url replaces the base url, like `base_url`. When a host cannot be reached (connection failure or
+    /// timeout), the request is sent to the next url of the chain; any response, even an error status, ends the chain.
+    /// If every host is unreachable, the request fails with `BancaDItaliaError::HostsUnreachable`. An empty chain keeps
+    /// the default base url.
+    ///
+    /// ## Arguments
+    /// - `base_urls`: The base urls, primary first, e.g. the Banca d'Italia url followed by a mirror.
+    pub fn base_urls(mut self, base_urls: Vec<String>) -> Self {
+        let mut base_urls = base_urls.into_iter();
+        if let Some(primary) = base_urls.next() {
+            self.base_url = Some(primary);
+            self.fallback_base_urls = base_urls
+                .map(|url| url.trim_end_matches('/').to_string())
+                .collect();
+        }
+        self
+    }
+
+    /// Sets how long idle connections are kept in the pool before being closed.
+    ///
+    /// ## Arguments
+    /// - `timeout`: The idle timeout of pooled connections.
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the maximum number of idle connections kept per host.
+    ///
+    /// ## Arguments
+    /// - `max`: The maximum number of idle connections per host.
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Forces HTTP/2 without negotiating it first.
+    ///
+    /// Only enable this option if the target server is known to support HTTP/2, otherwise every request fails.
+    ///
+    /// ## Arguments
+    /// - `enabled`: Whether HTTP/2 prior knowledge is used.
+    pub fn http2_prior_knowledge(mut self, enabled: bool) -> Self {
+        self.http2_prior_knowledge = enabled;
+        self
+    }
+
+    /// Sets whether redirects are followed.
+    ///
+    /// By default, up to 10 redirects are followed, like `reqwest`. Whatever the policy, a response redirected to a host
+    /// other than the requested one, or a redirect that is not followed, fails with
+    /// `BancaDItaliaError::UnexpectedRedirect` instead of being parsed as data.
+    ///
+    /// ## Arguments
+    /// - `enabled`: Whether redirects are followed.
+    pub fn follow_redirects(mut self, enabled: bool) -> Self {
+        self.follow_redirects = Some(enabled);
+        self
+    }
+
+    /// Sets the maximum number of redirects followed.
+    ///
+    /// ## Arguments
+    /// - `max`: The maximum number of redirects followed before the request fails.
+    pub fn max_redirects(mut self, max: usize) -> Self {
+        self.max_redirects = Some(max);
+        self
+    }
+
+    /// Rejects latest rates containing implausible values.
+    ///
+    /// When enabled, methods returning latest rates fail with `BancaDItaliaError::RateAnomalies` if any rate is negative
+    /// or above the plausible threshold (`DEFAULT_MAX_PLAUSIBLE_RATE` unless set with `max_plausible_rate`).
+    ///
+    /// ## Arguments
+    /// - `enabled`: Whether implausible rates are rejected.
+    pub fn reject_rate_anomalies(mut self, enabled: bool) -> Self {
+        self.reject_rate_anomalies = enabled;
+        self
+    }
+
+    /// Sets the largest plausible rate used by `reject_rate_anomalies`.
+    ///
+    /// ## Arguments
+    /// - `max_rate`: The largest plausible `eur_rate` or `usd_rate`.
+    pub fn max_plausible_rate(mut self, max_rate: Decimal) -> Self {
+        self.max_plausible_rate = Some(max_rate);
+        self
+    }
+
+    /// Reports unavailable rates as errors.
+    ///
+    /// By default an unavailable (`N.A.`) rate is parsed as zero, which helpers such as `eur_per_unit` treat as missing.
+    /// When enabled, methods returning latest rates fail with `BancaDItaliaError::RateUnavailable` on the first
+    /// unavailable `eurRate` or `usdRate`. Since the latest rates table usually lists a few currencies without a rate,
+    /// this is meant for pipelines that must not proceed with incomplete data.
+    ///
+    /// ## Arguments
+    /// - `enabled`: Whether unavailable rates are reported as errors.
+    pub fn strict_rates(mut self, enabled: bool) -> Self {
+        self.strict_rates = enabled;
+        self
+    }
+
+    /// Keeps the original string representation of the rates.
+    ///
+    /// When enabled, methods returning latest rates fill `LatestRate::raw_eur_rate` and `LatestRate::raw_usd_rate` with
+    /// the values exactly as published, e.g. `"0.86753"` or `"N.A."`, so parsed rates can be reconciled against source
+    /// files. Disabled by default to avoid storing every rate twice.
+    ///
+    /// ## Arguments
+    /// - `enabled`: Whether the original rates are kept.
+    pub fn keep_raw(mut self, enabled: bool) -> Self {
+        self.keep_raw = enabled;
+        self
+    }
+
+    /// Rejects responses containing fields the crate does not consume.
+    ///
+    /// When enabled, methods fetching currencies or latest rates fail with `BancaDItaliaError::UnknownField` if a record
+    /// has a field outside `CURRENCY_FIELDS`, `COUNTRY_FIELDS` or `LATEST_RATE_FIELDS`, so schema changes upstream are
+    /// caught early, e.g. in CI. Disabled by default, since new fields do not prevent parsing.
+    ///
+    /// ## Arguments
+    /// - `enabled`: Whether unknown fields are reported as errors.
+    pub fn deny_unknown_fields(mut self, enabled: bool) -> Self {
+        self.deny_unknown_fields = enabled;
+        self
+    }
+
+    /// Enables serving the last known latest rates when a live fetch fails.
+    ///
+    /// When enabled, `get_latest_rate_or_stale` stores every successful fetch in the snapshot shared with the
+    /// auto-refresh task, and answers a failed fetch with that snapshot flagged as `Staleness::Stale`. Without a prior
+    /// snapshot the fetch error is still returned. Disabled by default.
+    ///
+    /// ## Arguments
+    /// - `enabled`: Whether stale rates are served on fetch failure.
+    pub fn serve_stale_on_error(mut self, enabled: bool) -> Self {
+        self.serve_stale_on_error = enabled;
+        self
+    }
+
+    /// Enables collecting the iso codes listed in the latest rates but missing from the currency registry.
+    ///
+    /// When enabled, every method fetching both datasets (`reconcile`, `resolve_currency_name`) records these orphan
+    /// codes, which `orphan_codes` then returns, so upstream inconsistencies can be monitored during normal use instead
+    /// of through a separate audit. Disabled by default, so no comparison is made.
+    ///
+    /// ## Arguments
+    /// - `enabled`: Whether orphan iso codes are collected.
+    pub fn collect_orphan_codes(mut self, enabled: bool) -> Self {
+        self.collect_orphan_codes = enabled;
+        self
+    }
+
+    /// Sets a hook invoked after each request sent to Banca d'Italia.
+    ///
+    /// The hook receives the `RequestMetrics` of every request, including failed ones, and can be used to feed any
+    /// metrics system (Prometheus, StatsD, ...). It runs on the task performing the request, so it should return quickly.
+    ///
+    /// ## Arguments
+    /// - `hook`: The function invoked with the metrics of each request.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use bank_of_italy_api::{BancaDItalia, RequestMetrics};
+    ///
+    /// let boi = BancaDItalia::builder()
+    ///     .on_request(Box::new(|metrics: RequestMetrics| {
+    ///         println!("{} took {:?}", metrics.endpoint, metrics.duration);
+    ///     }))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn on_request(mut self, hook: Box<RequestHook>) -> Self {
+        self.on_request = Some(Arc::from(hook));
+        self
+    }
+
+    /// Sets the maximum number of requests in flight at the same time.
+    ///
+    /// The limit is shared by every clone of the built client, including the auto-refresh task, so concurrent callers
+    /// queue instead of overwhelming the API or the connection pool. Defaults to `DEFAULT_MAX_CONCURRENCY`; a value of
+    /// zero is treated as one.
+    ///
+    /// ## Arguments
+    /// - `max`: The maximum number of concurrent requests.
+    pub fn max_concurrency(mut self, max: usize) -> Self {
+        self.max_concurrency = Some(max);
+        self
+    }
+
+    /// Sets the publication schedule followed by `BancaDItalia::poll_latest_rate_smart`.
+    ///
+    /// ## Arguments
+    /// - `schedule`: The publication window and the polling interval within it.
+    pub fn publication_schedule(mut self, schedule: PublicationSchedule) -> Self {
+        self.publication_schedule = Some(schedule);
+        self
+    }
+
+    /// Sets the source of the current time.
+    ///
+    /// The client reads the time through the clock when recording `last_successful_fetch` and scheduling
+    /// `poll_latest_rate_smart`, so a `FixedClock` makes that behavior testable without sleeping. Defaults to
+    /// `SystemClock`.
+    ///
+    /// ## Arguments
+    /// - `clock`: The clock shared by every clone of the built client.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use bank_of_italy_api::{BancaDItalia, FixedClock};
+    /// use std::sync::Arc;
+    /// use time::macros::datetime;
+    ///
+    /// let clock = Arc::new(FixedClock::new(datetime!(2025-07-30 14:00 UTC)));
+    /// let boi = BancaDItalia::builder().clock(clock.clone()).build().unwrap();
+    /// ```
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Builds the Banca d'Italia client.
+    ///
+    /// ## Returns
+    /// - `Ok(BancaDItalia)`: The configured client.
+    /// - `Err(BancaDItaliaError)`: If the underlying `reqwest` client cannot be built.
+    pub fn build(self) -> Result<BancaDItalia, BancaDItaliaError> {
+        let mut client = Client::builder();
+        if let Some(timeout) = self.pool_idle_timeout {
+            client = client.pool_idle_timeout(timeout);
+        }
+        if let Some(max) = self.pool_max_idle_per_host {
+            client = client.pool_max_idle_per_host(max);
+        }
+        if self.http2_prior_knowledge {
+            client = client.http2_prior_knowledge();
+        }
+        if self.follow_redirects == Some(false) {
+            client = client.redirect(reqwest::redirect::Policy::none());
+        } else if let Some(max) = self.max_redirects {
+            client = client.redirect(reqwest::redirect::Policy::limited(max));
+        }
+        let client = client.build().map_err(BancaDItaliaError::RequestFailed)?;
+        Ok(self.finish(HttpClient::Reqwest(client)))
+    }
+
+    /// Builds a Banca d'Italia client answering requests from an in-memory transport.
+    ///
+    /// The connection options do not apply, since no HTTP client is built. Requires the `test-util` feature.
+    ///
+    /// ## Arguments
+    /// - `transport`: The transport holding the canned responses.
+    ///
+    /// ## Returns
+    /// - `BancaDItalia`: The configured client.
+    #[cfg(feature = "test-util")]
+    pub fn build_with_mock(self, transport: MockTransport) -> BancaDItalia {
+        self.finish(HttpClient::Mock(Arc::new(transport)))
+    }
+
+    /// Creates the Banca d'Italia client around an already built HTTP client.
+    ///
+    /// ## Arguments
+    /// - `client`: The HTTP client used to send requests.
+    ///
+    /// ## Returns
+    /// - `BancaDItalia`: The configured client.
+    fn finish(self, client: HttpClient) -> BancaDItalia {
+        BancaDItalia {
+            client,
+            fallback_base_urls: self.fallback_base_urls,
+            base_url: resolve_base_url(self.base_url, std::env::var(BASE_URL_ENV).ok()),
+            snapshot: Arc::new(ArcSwapOption::empty()),
+            last_success: Arc::new(ArcSwapOption::empty()),
+            currency_names: Arc::new(ArcSwapOption::empty()),
+            max_plausible_rate: self.reject_rate_anomalies.then(|| {
+                self.max_plausible_rate
+                    .unwrap_or(DEFAULT_MAX_PLAUSIBLE_RATE)
+            }),
+            strict_rates: self.strict_rates,
+            keep_raw: self.keep_raw,
+            deny_unknown_fields: self.deny_unknown_fields,
+            serve_stale_on_error: self.serve_stale_on_error,
+            orphan_codes: self
+                .collect_orphan_codes
+                .then(|| Arc::new(Mutex::new(BTreeSet::new()))),
+            on_request: self.on_request,
+            limiter: Arc::new(Semaphore::new(
+                self.max_concurrency
+                    .unwrap_or(DEFAULT_MAX_CONCURRENCY)
+                    .max(1),
+            )),
+            publication_schedule: self.publication_schedule.unwrap_or_default(),
+            clock: self.clock.unwrap_or_else(|| Arc::new(SystemClock)),
+        }
+    }
+}
+
+/// Represents the HTTP client used to send requests to Banca d'Italia.
+#[derive(Clone)]
+enum HttpClient {
+    /// A plain `reqwest` client.
+    Reqwest(Client),
+    /// A `reqwest` client wrapped in a middleware stack.
+    #[cfg(feature = "reqwest-middleware")]
+    Middleware(reqwest_middleware::ClientWithMiddleware),
+    /// An in-memory transport answering with canned responses.
+    #[cfg(feature = "test-util")]
+    Mock(Arc<MockTransport>),
+}
+
+impl HttpClient {
+    /// Sends a GET request.
+    ///
+    /// ## Arguments
+    /// - `url`: The url to request.
+    /// - `format`: The format requested through the `Accept` header.
+    ///
+    /// ## Returns
+    /// - `Ok(reqwest::Response)`: The response, with its body not consumed yet.
+    /// - `Err(BancaDItaliaError)`: If the request fails or is redirected, see `check_redirect`.
+    async fn get(
+        &self,
+        url: &str,
+        format: ResponseFormat,
+    ) -> Result<reqwest::Response, BancaDItaliaError> {
+        let accept = format.accept();
+        let response = match self {
+            HttpClient::Reqwest(client) => client.get(url).header("Accept", accept).send().await?,
+            #[cfg(feature = "reqwest-middleware")]
+            HttpClient::Middleware(client) => {
+                client.get(url).header("Accept", accept).send().await?
+            }
+            #[cfg(feature = "test-util")]
+            HttpClient::Mock(transport) => transport.respond(url, format)?,
+        };
+        check_redirect(url, response)
+    }
+}
+
+/// Checks that a response was not redirected away from the API.
+///
+/// A redirect that was not followed (see `BancaDItaliaBuilder::follow_redirects`) or that was followed to another host,
+/// e.g. a maintenance page, would otherwise be parsed as data.
+///
+/// ## Arguments
+/// - `url`: The url requested.
+/// - `response`: The response received.
+///
+/// ## Returns
+/// - `Ok(reqwest::Response)`: The response, if it comes from the requested host.
+/// - `Err(BancaDItaliaError)`: `UnexpectedRedirect` with the redirect target otherwise.
+fn check_redirect(
+    url: &str,
+    response: reqwest::Response,
+) -> Result<reqwest::Response, BancaDItaliaError> {
+    if response.status().is_redirection() {
+        let to = response
+            .headers()
+            .get(reqwest::header::LOCATION)
+            .and_then(|location| location.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        return Err(BancaDItaliaError::UnexpectedRedirect { to });
+    }
+    let requested_host = reqwest::Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string));
+    if requested_host.is_some() && response.url().host_str() != requested_host.as_deref() {
+        return Err(BancaDItaliaError::UnexpectedRedirect {
+            to: response.url().to_string(),
+        });
+    }
+    Ok(response)
+}
+
+/// Resolves the base url of a client.
+///
+/// ## Arguments
+/// - `configured`: The url set with `BancaDItaliaBuilder::base_url`, if any.
+/// - `env`: The value of the `BOI_BASE_URL` environment variable, if set.
+///
+/// ## Returns
+/// - `String`: The configured url, else the non-empty environment value, else the Banca d'Italia API, without trailing
+///   slashes.
+fn resolve_base_url(configured: Option<String>, env: Option<String>) -> String {
+    configured
+        .or_else(|| env.filter(|url| !url.is_empty()))
+        .unwrap_or_else(|| BOI_BASE_URL.to_string())
+        .trim_end_matches('/')
+        .to_string()
+}
+
+/// Checks whether a request failed because the host could not be reached.
+///
+/// ## Arguments
+/// - `err`: The error of the request.
+///
+/// ## Returns
+/// - `bool`: Whether the error is a connection failure or a timeout.
+fn is_connection_failure(err: &BancaDItaliaError) -> bool {
+    match err {
+        BancaDItaliaError::RequestFailed(err) => err.is_connect() || err.is_timeout(),
+        #[cfg(feature = "reqwest-middleware")]
+        BancaDItaliaError::Middleware(reqwest_middleware::Error::Reqwest(err)) => {
+            err.is_connect() || err.is_timeout()
+        }
+        _ => false,
+    }
+}
+
+/// Represents the formats in which Banca d'Italia can return data.
+///
+/// Every endpoint returns JSON, the only format mapped into typed structs. Banca d'Italia also documents CSV and PDF
+/// renderings of its endpoints (see the [Exchange Rate API docs](https://www.bancaditalia.it/compiti/operazioni-cambi/Operating_Instructions.pdf?language_id=1));
+/// the formats offered by each endpoint are listed in `EndpointInfo::formats`. When an endpoint does not offer the
+/// requested format, the server answers with an error status, reported as `BancaDItaliaError::ApiError`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ResponseFormat {
+    /// JSON documents, parsed by the typed methods.
+    Json,
+    /// Comma separated values.
+    Csv,
+    /// PDF documents.
+    Pdf,
+}
+
+impl ResponseFormat {
+    /// Returns the value of the `Accept` header requesting the format.
+    pub fn accept(&self) -> &'static str {
+        match self {
+            ResponseFormat::Json => "application/json",
+            ResponseFormat::Csv => "text/csv",
+            ResponseFormat::Pdf => "application/pdf",
+        }
+    }
+}
+
+/// Represents the endpoints of the Banca d'Italia API wrapped by the crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Endpoint {
+    /// The registry of currencies, fetched by `get_currencies`.
+    Currencies,
+    /// The latest exchange rates, fetched by `get_latest_rate`.
+    LatestRates,
+}
+
+impl Endpoint {
+    /// Every endpoint wrapped by the crate.
+    pub const ALL: [Endpoint; 2] = [Endpoint::Currencies, Endpoint::LatestRates];
+
+    /// Returns the description of the endpoint.
+    pub const fn info(&self) -> EndpointInfo {
+        match self {
+            Endpoint::Currencies => EndpointInfo {
+                name: "currencies",
+                path: "/currencies",
+                required_params: &["lang"],
+                formats: &[ResponseFormat::Json, ResponseFormat::Csv],
+            },
+            Endpoint::LatestRates => EndpointInfo {
+                name: "latestRates",
+                path: "/latestRates",
+                required_params: &["lang"],
+                formats: &[ResponseFormat::Json, ResponseFormat::Csv],
+            },
+        }
+    }
+
+    /// Returns the path of the endpoint, relative to the base url.
+    pub const fn path(&self) -> &'static str {
+        self.info().path
+    }
+}
+
+/// Describes an endpoint wrapped by the crate, as listed by `BancaDItalia::endpoints`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct EndpointInfo {
+    /// The name of the endpoint, which is also the access key of its data, e.g. `latestRates`.
+    pub name: &'static str,
+    /// The path of the endpoint, relative to the base url, e.g. `/latestRates`.
+    pub path: &'static str,
+    /// The query parameters sent with every request to the endpoint.
+    pub required_params: &'static [&'static str],
+    /// The formats in which the endpoint can be requested. Neither endpoint is served as PDF.
+    pub formats: &'static [ResponseFormat],
+}
+
+/// The descriptions of `Endpoint::ALL`, returned by `BancaDItalia::endpoints`.
+static ENDPOINTS: [EndpointInfo; Endpoint::ALL.len()] = {
+    let mut infos = [Endpoint::Currencies.info(); Endpoint::ALL.len()];
+    let mut index = 0;
+    while index < infos.len() {
+        infos[index] = Endpoint::ALL[index].info();
+        index += 1;
+    }
+    infos
+};
+
+/// Represents a response body decoded according to its `ResponseFormat`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormattedPayload {
+    /// The JSON document, for `ResponseFormat::Json`.
+    Json(serde_json::Value),
+    /// The CSV text, for `ResponseFormat::Csv`.
+    Csv(String),
+    /// The PDF bytes, for `ResponseFormat::Pdf`.
+    Pdf(Vec<u8>),
+}
+
+/// The type of the hook invoked by the client after each request.
+pub type RequestHook = dyn Fn(RequestMetrics) + Send + Sync;
+
+/// Represents the metrics of a single request sent to Banca d'Italia.
+#[derive(Debug, Clone)]
+pub struct RequestMetrics {
+    /// The endpoint requested, e.g. `latestRates`.
+    pub endpoint: String,
+    /// The time elapsed between sending the request and reading the whole body.
+    pub duration: Duration,
+    /// The HTTP status code, `None` if no response was received.
+    pub status: Option<u16>,
+    /// The size of the response body in bytes, zero if it could not be read.
+    pub bytes: usize,
+}
+
+/// Extracts the endpoint name from a Banca d'Italia url.
+///
+/// ## Arguments
+/// - `url`: The url to data endpoint.
+///
+/// ## Returns
+/// - `&str`: The last path segment of the url, without the query string.
+fn endpoint_name(url: &str) -> &str {
+    let path = url.split('?').next().unwrap_or(url);
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// Represents an implausible rate found by `BancaDItalia::find_rate_anomalies`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RateAnomaly {
+    /// The isocode of the currency.
+    pub isocode: String,
+    /// The name of the rate field, `eurRate` or `usdRate`.
+    pub field: String,
+    /// The implausible value.
+    pub value: Decimal,
+    /// Why the value is implausible.
+    pub kind: RateAnomalyKind,
+}
+
+/// Represents why a rate is considered implausible.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum RateAnomalyKind {
+    /// The rate is negative.
+    Negative,
+    /// The rate is above the plausible threshold.
+    AboveThreshold,
+}
+
+/// Represents when Banca d'Italia is expected to publish the latest rates.
+///
+/// The default window runs from 13:30 to 17:30 at UTC+01:00, wide enough to cover the afternoon publication both in
+/// winter (CET) and in summer (CEST), with a poll every 5 minutes within it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublicationSchedule {
+    /// The time from which the rates of the day may be published.
+    pub window_start: Time,
+    /// The time after which the rates of the day are no longer expected.
+    pub window_end: Time,
+    /// The offset of `window_start` and `window_end`.
+    pub utc_offset: UtcOffset,
+    /// The time between two polls within the window.
+    pub interval: Duration,
+}
+
+impl Default for PublicationSchedule {
+    fn default() -> Self {
+        PublicationSchedule {
+            window_start: time::macros::time!(13:30),
+            window_end: time::macros::time!(17:30),
+            utc_offset: time::macros::offset!(+1),
+            interval: Duration::from_secs(300),
+        }
+    }
+}
+
+/// Computes how long `BancaDItalia::poll_latest_rate_smart` waits before the next poll.
+///
+/// ## Arguments
+/// - `now`: The current time.
+/// - `latest`: The most recent reference date already retrieved, if any.
+/// - `schedule`: The publication schedule.
+///
+/// ## Returns
+/// - `Duration`: The polling interval within the window of a business day whose rates are still missing, the time
+///   until the next window otherwise.
+fn next_poll_delay(
+    now: OffsetDateTime,
+    latest: Option<Date>,
+    schedule: &PublicationSchedule,
+) -> Duration {
+    let is_business_day =
+        |date: Date| !matches!(date.weekday(), Weekday::Saturday | Weekday::Sunday);
+    let now = now.to_offset(schedule.utc_offset);
+    let today = now.date();
+    let published = latest.is_some_and(|date| date >= today);
+
+    let mut day = today;
+    if published || !is_business_day(today) || now.time() >= schedule.window_end {
+        loop {
+            day = match day.next_day() {
+                Some(next) => next,
+                None => return schedule.interval,
+            };
+            if is_business_day(day) {
+                break;
+            }
+        }
+    } else if now.time() >= schedule.window_start {
+        return schedule.interval;
+    }
+    let window_start = day
+        .with_time(schedule.window_start)
+        .assume_offset(schedule.utc_offset);
+    Duration::try_from(window_start - now).unwrap_or(schedule.interval)
+}
+
+/// A handle to the background task started by `BancaDItalia::spawn_auto_refresh` or
+/// `BancaDItalia::poll_latest_rate_smart`.
+///
+/// The background task is aborted when the handle is dropped, so it never outlives its handle.
+pub struct RefreshHandle {
+    /// The background refresh task.
+    task: JoinHandle<()>,
+}
+
+impl RefreshHandle {
+    /// Stops the background refresh task.
+    ///
+    /// The snapshot keeps the last refreshed rates after the task is stopped.
+    pub fn stop(self) {
+        self.task.abort();
+    }
+}
+
+impl Drop for RefreshHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Extracts the data stored under the access key of a Banca d'Italia response.
+///
+/// The function deserializes the top level of the response into borrowed raw values, so only the data stored under the
+/// access key is deserialized into the target type, without building an intermediate `serde_json::Value`.
+///
+/// ## Arguments
+/// - `payload`: The raw JSON response returned by the API.
+/// - `access_key`: The access key that allows to access data stored in JSON structure.
+///
+/// ## Returns
+/// - `Ok(Vec<DeserializeOwned>)`: A vector composed by the deserialized data, empty when the access key holds an empty
+///   array.
+/// - `Err(BancaDItaliaError)`: `MissingKey` if the access key is missing, listing the keys found instead, `NoResult` if
+///   it holds `null`, or an error if the data cannot be deserialized.
+fn extract_data<T: DeserializeOwned>(
+    payload: &[u8],
+    access_key: &str,
+) -> Result<Vec<T>, BancaDItaliaError> {
+    let mut envelope = serde_json::from_slice::<HashMap<String, &RawValue>>(payload)?;
+    let data = envelope.remove(access_key).ok_or_else(|| {
+        let mut available_keys = envelope.keys().cloned().collect::<Vec<String>>();
+        available_keys.sort();
+        BancaDItaliaError::MissingKey {
+            key: access_key.to_string(),
+            available_keys,
+        }
+    })?;
+    serde_json::from_str::<Option<Vec<T>>>(data.get())?.ok_or(BancaDItaliaError::NoResult)
+}
+
+/// Checks that the records stored under the access key only contain the fields consumed by the crate.
+///
+/// ## Arguments
+/// - `payload`: The raw JSON response body.
+/// - `access_key`: The access key of the data, `currencies` or `latestRates`.
+///
+/// ## Returns
+/// - `Ok(())`: If every field is known, or the access key has no known field list.
+/// - `Err(BancaDItaliaError)`: If a field is unknown, reported with its path, e.g. `currencies.countries.region`.
+fn check_known_fields(payload: &[u8], access_key: &str) -> Result<(), BancaDItaliaError> {
+    let fields = match access_key {
+        "currencies" => CURRENCY_FIELDS,
+        "latestRates" => LATEST_RATE_FIELDS,
+        _ => return Ok(()),
+    };
+    let unknown =
+        |path: String, record: &serde_json::Map<String, serde_json::Value>, known: &[&str]| {
+            record
+                .keys()
+                .find(|key| !known.contains(&key.as_str()))
+                .map(|key| BancaDItaliaError::UnknownField {
+                    field: format!("{}.{}", path, key),
+                })
+        };
+    let records: Vec<serde_json::Map<String, serde_json::Value>> =
+        extract_data(payload, access_key).or_else(|err| match err {
+            BancaDItaliaError::NoResult => Ok(Vec::new()),
+            err => Err(err),
+        })?;
+    for record in &records {
+        if let Some(err) = unknown(access_key.to_string(), record, fields) {
+            return Err(err);
+        }
+        let countries = record.get("countries").and_then(|c| c.as_array());
+        for country in countries
+            .into_iter()
+            .flatten()
+            .filter_map(|c| c.as_object())
+        {
+            if let Some(err) = unknown(format!("{}.countries", access_key), country, COUNTRY_FIELDS)
+            {
+                return Err(err);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Represents the information about data returned by the Banca d'Italia API.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ResultInfo {
+    /// The number of data point returned by the query.
+    #[serde(rename = "totalRecords")]
+    pub total_records: i32,
+    /// The timezone used in the data.
+    #[serde(rename = "timezoneReference")]
+    pub timezone_reference: String,
+    /// Any additional information about data.
+    pub notice: String,
+}
+
+impl ResultInfo {
+    /// Parses the `notice` text into structured flags.
+    ///
+    /// The notice is split on new lines and semicolons, and every part is matched against known wordings. Parts that do
+    /// not match any known wording are returned as `Notice::Other`, so no information is lost.
+    ///
+    /// ## Returns
+    /// - `Vec<Notice>`: The notices found in the text, in order. Empty if there is no notice.
+    pub fn notices(&self) -> Vec<Notice> {
+        self.notice
+            .split(['\n', ';'])
+            .map(str::trim)
+            .filter(|part| !part.is_empty())
+            .map(Notice::parse)
+            .collect()
+    }
+
+    /// Parses the `timezone_reference` text into its standard UTC offset.
+    ///
+    /// The API currently reports `Dates refer to the Central European Time Zone`. Both the zone name and the `CET`, `CEST`,
+    /// `UTC` and `GMT` abbreviations are recognized. For the Central European zone, the standard (winter) offset is
+    /// returned; use `offset_on` to account for daylight saving time.
+    ///
+    /// ## Returns
+    /// - `Ok(UtcOffset)`: The standard offset of the zone, e.g. `+01:00` for Central European Time.
+    /// - `Err(BancaDItaliaError)`: If the timezone reference is not recognized.
+    pub fn offset(&self) -> Result<UtcOffset, BancaDItaliaError> {
+        Ok(self.timezone()?.0)
+    }
+
+    /// Parses the `timezone_reference` text into the UTC offset in force on a date.
+    ///
+    /// For the Central European zone, the summer offset (`+02:00`) applies from the last Sunday of March to the day
+    /// before the last Sunday of October, following the European Union daylight saving rules; the hours around the switch
+    /// are not distinguished. A reference naming `CEST` explicitly always yields `+02:00`.
+    ///
+    /// ## Arguments
+    /// - `date`: The date the offset applies to, e.g. a `reference_date`.
+    ///
+    /// ## Returns
+    /// - `Ok(UtcOffset)`: The offset in force on `date`.
+    /// - `Err(BancaDItaliaError)`: If the timezone reference is not recognized.
+    pub fn offset_on(&self, date: Date) -> Result<UtcOffset, BancaDItaliaError> {
+        let (offset, observes_dst) = self.timezone()?;
+        if !observes_dst {
+            return Ok(offset);
+        }
+        let last_sunday = |month: time::Month| {
+            let mut day = Date::from_calendar_date(date.year(), month, month.length(date.year()))
+                .expect("the last day of a month is a valid date");
+            while day.weekday() != Weekday::Sunday {
+                day = day
+                    .previous_day()
+                    .expect("the previous day of a month end is a valid date");
+            }
+            day
+        };
+        if date >= last_sunday(time::Month::March) && date < last_sunday(time::Month::October) {
+            return Ok(time::macros::offset!(+2));
+        }
+        Ok(offset)
+    }
+
+    /// Recognizes the timezone named by `timezone_reference`.
+    ///
+    /// ## Returns
+    /// - `Ok((UtcOffset, bool))`: The standard offset and whether daylight saving time applies.
+    /// - `Err(BancaDItaliaError)`: If the timezone reference is not recognized.
+    fn timezone(&self) -> Result<(UtcOffset, bool), BancaDItaliaError> {
+        let reference = self.timezone_reference.to_uppercase();
+        let words: Vec<&str> = reference
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .collect();
+        if words.contains(&"CEST") || reference.contains("CENTRAL EUROPEAN SUMMER") {
+            Ok((time::macros::offset!(+2), false))
+        } else if words.contains(&"CET") || reference.contains("CENTRAL EUROPEAN") {
+            Ok((time::macros::offset!(+1), true))
+        } else if words.contains(&"UTC") || words.contains(&"GMT") {
+            Ok((UtcOffset::UTC, false))
+        } else {
+            Err(BancaDItaliaError::ApiError(format!(
+                "Unknown timezone reference: {}",
+                self.timezone_reference
+            )))
+        }
+    }
+}
+
+/// Represents a single condition reported in the `notice` field of the API response.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Notice {
+    /// The data is provisional and may be revised.
+    ProvisionalData,
+    /// The publication of the data is delayed.
+    Delayed,
+    /// No rates have been published today.
+    NoPublicationToday,
+    /// Any notice the crate does not recognize.
+    Other(String),
+}
+
+impl Notice {
+    /// Matches a single notice against the known wordings.
+    fn parse(text: &str) -> Self {
+        let lower = text.to_lowercase();
+        if lower.contains("provisional") {
+            Notice::ProvisionalData
+        } else if lower.contains("delay") {
+            Notice::Delayed
+        } else if lower.contains("no publication")
+            || lower.contains("not published")
+            || lower.contains("not been published")
+        {
+            Notice::NoPublicationToday
+        } else {
+            Notice::Other(text.to_string())
+        }
+    }
+}
+
+/// Represents the metadata about the results returned by the API.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct MetaData {
+    /// The results informations.
+    #[serde(rename = "resultsInfo")]
+    pub results_info: ResultInfo,
+}
+
+/// Represents the latest exchange rates together with the metadata of the response they come from.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RatesReport {
+    /// The information about the response.
+    pub results_info: ResultInfo,
+    /// The latest exchange rates.
+    pub latest_rates: Vec<LatestRate>,
+}
+
+/// Represents the vector containing the currencies data.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Currencies {
+    /// The vector containing the currencies listed in the data.
+    pub currencies: Vec<Currency>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CurrencyAPI {
+    /// The country data of the currency, empty when the API omits the field.
+    #[serde(default)]
+    pub countries: Vec<CountryAPI>,
+    #[serde(rename = "isoCode")]
+    pub isocode: String,
     pub name: String,
+    /// Whether the currency has a chart on the Banca d'Italia portal, `false` when the API omits the field.
+    #[serde(default)]
     pub graph: bool,
 }
 
 /// Represents the single currency object.
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
 pub struct Currency {
     /// The country data of the currency.
     pub countries: Vec<Country>,
@@ -241,8 +2937,90 @@ pub struct Currency {
     pub graph: bool,
 }
 
+impl Currency {
+    /// Serializes the currency into a JSON string.
+    ///
+    /// Dates are serialized as `time::Date` values, not as the original API strings, so the output must be read back
+    /// with `Currency::from_json`.
+    ///
+    /// ## Returns
+    /// - `Ok(String)`: The JSON representation of the currency.
+    /// - `Err(BancaDItaliaError)`: If the serialization fails.
+    pub fn to_json(&self) -> Result<String, BancaDItaliaError> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Deserializes a currency from a JSON string produced by `Currency::to_json`.
+    ///
+    /// ## Arguments
+    /// - `json`: The JSON representation of the currency.
+    ///
+    /// ## Returns
+    /// - `Ok(Currency)`: The deserialized currency.
+    /// - `Err(BancaDItaliaError)`: If the deserialization fails.
+    pub fn from_json(json: &str) -> Result<Self, BancaDItaliaError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Returns the symbol of the currency, e.g. `€` for `EUR`.
+    ///
+    /// The symbol comes from a table bundled with the crate, since Banca d'Italia does not publish symbols. Coverage is
+    /// best-effort for major currencies. Requires the `symbols` feature.
+    ///
+    /// ## Returns
+    /// - `Some(&str)`: The symbol of the currency.
+    /// - `None`: If the currency is not in the bundled table.
+    #[cfg(feature = "symbols")]
+    pub fn symbol(&self) -> Option<&str> {
+        symbols::symbol_for(&self.isocode)
+    }
+
+    /// Returns when and where the currency was valid.
+    ///
+    /// The validity windows of each country are merged when they overlap or touch, i.e. when a window starts no later
+    /// than the day after the previous one ends, so a country listed several times for contiguous periods appears once. A `None` end date means the currency is still valid.
+    ///
+    /// ## Returns
+    /// - `Vec<(Date, Option<Date>, String)>`: The `(start, end, country)` windows sorted by start date, then country.
+    pub fn validity_timeline(&self) -> Vec<(Date, Option<Date>, String)> {
+        let mut windows: Vec<(&str, Date, Option<Date>)> = self
+            .countries
+            .iter()
+            .map(|c| {
+                (
+                    c.country.as_str(),
+                    c.validity_start_date,
+                    c.validity_end_date,
+                )
+            })
+            .collect();
+        windows.sort();
+
+        let mut timeline: Vec<(Date, Option<Date>, String)> = Vec::new();
+        let mut previous_country = None;
+        for (country, start, end) in windows {
+            if let Some(last) = timeline.last_mut() {
+                let touches = last.1.is_none_or(|last_end| {
+                    last_end.next_day().is_none_or(|next_day| start <= next_day)
+                });
+                if previous_country == Some(country) && touches {
+                    last.1 = match (last.1, end) {
+                        (Some(last_end), Some(end)) => Some(last_end.max(end)),
+                        _ => None,
+                    };
+                    continue;
+                }
+            }
+            timeline.push((start, end, country.to_string()));
+            previous_country = Some(country);
+        }
+        timeline.sort_by(|a, b| (a.0, &a.2).cmp(&(b.0, &b.2)));
+        timeline
+    }
+}
+
 /// Represents country information of the currency listed.
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
 pub struct Country {
     /// The isocode of the currency.
     #[serde(rename = "currencyISO")]
@@ -260,6 +3038,30 @@ pub struct Country {
     pub validity_end_date: Option<Date>,
 }
 
+impl Country {
+    /// Returns whether the country currently uses the euro, according to the Banca d'Italia registry.
+    ///
+    /// A country is in the euro zone when the entry is for the `EUR` currency and its validity window is still open (no
+    /// end date). Entries for the legacy national currencies replaced by the euro have a closed window and are not.
+    /// Note that the registry also lists the European Monetary Union itself as a country of the euro.
+    pub fn is_eurozone(&self) -> bool {
+        self.currencyiso.eq_ignore_ascii_case("EUR") && self.validity_end_date.is_none()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl Country {
+    /// Returns the validity start date as a `chrono::NaiveDate`.
+    pub fn validity_start_date_chrono(&self) -> chrono::NaiveDate {
+        to_naive_date(self.validity_start_date)
+    }
+
+    /// Returns the validity end date as a `chrono::NaiveDate`.
+    pub fn validity_end_date_chrono(&self) -> Option<chrono::NaiveDate> {
+        self.validity_end_date.map(to_naive_date)
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct CountryAPI {
     /// The isocode of the currency.
@@ -280,7 +3082,13 @@ pub struct CountryAPI {
 
 /// Converts the currencies method's results to use date instead of string.
 ///
-/// The function converts the `CurrencyAPI` struct into a `Currency` struct so it uses date instead of string.
+/// The function converts the `CurrencyAPI` struct into a `Currency` struct so it uses date instead of string. Countries
+/// without a validity start date, or whose validity end date precedes their validity start date, are skipped and reported
+/// with a `tracing` warning. An empty validity end date is treated as missing (still valid).
+///
+/// The validity start date is parsed as the start of its day and the validity end date as the end of its day, so the
+/// window covers both boundary days. Both are parsed in UTC and only their calendar day is kept, so the resulting `Date`
+/// is the one published by the API whichever `DateType` is used.
 ///
 /// ## Arguments
 /// - `currencies`: The vector resulting after fetching data from Banca d'Italia API.  
@@ -292,9 +3100,10 @@ fn parse_currency(currencies: Vec<CurrencyAPI>) -> Result<Vec<Currency>, BancaDI
     let result = currencies
         .into_iter()
         .map(|cur| {
-            let countries = cur
+            let mut countries = cur
                 .countries
                 .into_iter()
+                .filter(has_start_date)
                 .map(|c| {
                     Ok(Country {
                         currencyiso: c.currencyiso,
@@ -302,19 +3111,22 @@ fn parse_currency(currencies: Vec<CurrencyAPI>) -> Result<Vec<Currency>, BancaDI
                         countryiso: c.countryiso,
                         validity_start_date: parse_to_datetime(
                             &c.validity_start_date,
-                            DateType::End,
+                            DateType::Start,
                             OffsetType::Utc,
                         )?
                         .date(),
                         validity_end_date: c
                             .validity_end_date
                             .as_deref()
+                            .map(str::trim)
+                            .filter(|d| !d.is_empty())
                             .map(|d| parse_to_datetime(d, DateType::End, OffsetType::Utc))
                             .transpose()?
                             .map(|date| date.date()),
                     })
                 })
                 .collect::<Result<Vec<Country>, BancaDItaliaError>>()?;
+            countries.retain(has_valid_window);
 
             Ok(Currency {
                 countries,
@@ -325,11 +3137,183 @@ fn parse_currency(currencies: Vec<CurrencyAPI>) -> Result<Vec<Currency>, BancaDI
         })
         .collect::<Result<Vec<Currency>, BancaDItaliaError>>()?;
 
-    Ok(result)
+    Ok(result)
+}
+
+/// Checks that a country returned by the API has a validity start date.
+///
+/// ## Arguments
+/// - `country`: The country to check.
+///
+/// ## Returns
+/// - `bool`: `false` if the validity start date is an empty string, `true` otherwise.
+fn has_start_date(country: &CountryAPI) -> bool {
+    let valid = !country.validity_start_date.trim().is_empty();
+    if !valid {
+        tracing::warn!(
+            currency = %country.currencyiso,
+            country = %country.country,
+            "skipping country with empty validity start date"
+        );
+    }
+    valid
+}
+
+/// Checks that the validity window of a country is not inverted.
+///
+/// ## Arguments
+/// - `country`: The country to check.
+///
+/// ## Returns
+/// - `bool`: `false` if the validity end date precedes the validity start date, `true` otherwise.
+fn has_valid_window(country: &Country) -> bool {
+    let valid = country
+        .validity_end_date
+        .is_none_or(|end| end >= country.validity_start_date);
+    if !valid {
+        tracing::warn!(
+            currency = %country.currencyiso,
+            country = %country.country,
+            start = %country.validity_start_date,
+            end = ?country.validity_end_date,
+            "skipping country with validity end date before validity start date"
+        );
+    }
+    valid
+}
+
+/// Represents the comparison between the currency registry and the latest exchange rates.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Reconciliation {
+    /// The iso codes listed in the registry but missing from the latest rates.
+    pub only_in_registry: Vec<String>,
+    /// The iso codes listed in the latest rates but missing from the registry.
+    pub only_in_rates: Vec<String>,
+    /// The number of iso codes found in both datasets.
+    pub in_both: usize,
+}
+
+/// Represents the outcome of `BancaDItalia::self_test`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SelfTestReport {
+    /// The outcome of each endpoint, in a stable order.
+    pub endpoints: Vec<EndpointCheck>,
+}
+
+impl SelfTestReport {
+    /// Returns whether every endpoint returned data that the crate could parse.
+    pub fn is_healthy(&self) -> bool {
+        self.endpoints
+            .iter()
+            .all(|check| matches!(check.outcome, EndpointOutcome::Ok { .. }))
+    }
+}
+
+/// Represents the outcome of a single endpoint checked by `BancaDItalia::self_test`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct EndpointCheck {
+    /// The name of the endpoint, e.g. `latestRates`.
+    pub endpoint: String,
+    /// The outcome of the request.
+    pub outcome: EndpointOutcome,
+}
+
+/// Represents how an endpoint responded to `BancaDItalia::self_test`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub enum EndpointOutcome {
+    /// The response was parsed and contained the given number of records.
+    Ok { records: usize },
+    /// The response was parsed but contained no records.
+    Empty,
+    /// The response did not match the structs of the crate, or its values could not be mapped, e.g. a malformed date.
+    ParseFailed(String),
+    /// The request itself failed, or was answered with an error status.
+    RequestFailed(String),
+}
+
+/// Represents data returned by a fetch that may fall back to the last known values.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Staleness<T> {
+    /// The data has just been fetched from Banca d'Italia servers.
+    Fresh(T),
+    /// The fetch failed and the data comes from the last successful one.
+    Stale(T),
+}
+
+impl<T> Staleness<T> {
+    /// Returns whether the data comes from a previous fetch.
+    pub fn is_stale(&self) -> bool {
+        matches!(self, Staleness::Stale(_))
+    }
+
+    /// Returns a reference to the data, fresh or stale.
+    pub fn get(&self) -> &T {
+        match self {
+            Staleness::Fresh(data) | Staleness::Stale(data) => data,
+        }
+    }
+
+    /// Returns the data, fresh or stale.
+    pub fn into_inner(self) -> T {
+        match self {
+            Staleness::Fresh(data) | Staleness::Stale(data) => data,
+        }
+    }
+}
+
+/// Represents the currencies added to or removed from the registry, as found by `BancaDItalia::registry_diff`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RegistryDiff {
+    /// The iso codes listed in the new registry only.
+    pub added: Vec<String>,
+    /// The iso codes listed in the old registry only.
+    pub removed: Vec<String>,
+}
+
+impl RegistryDiff {
+    /// Returns whether the two registries list the same currencies.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Represents a (currency, country) pair of the registry, as flattened by `BancaDItalia::registry_rows`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RegistryRow {
+    /// The iso code of the currency.
+    pub isocode: String,
+    /// The name of the currency.
+    pub currency_name: String,
+    /// The country using the currency.
+    pub country: String,
+    /// The iso code of the country, `None` for entries without one.
+    pub country_iso: Option<String>,
+    /// The date the country started using the currency.
+    pub validity_start_date: Date,
+    /// The date the country stopped using the currency, `None` while still in use.
+    pub validity_end_date: Option<Date>,
+}
+
+/// Compares the iso codes of the currency registry with the ones of the latest rates.
+///
+/// ## Arguments
+/// - `currencies`: The currency registry.
+/// - `latest_rates`: The latest exchange rates.
+///
+/// ## Returns
+/// - `Reconciliation`: The iso codes found in only one of the datasets, sorted alphabetically.
+fn reconcile_isocodes(currencies: &[Currency], latest_rates: &[LatestRate]) -> Reconciliation {
+    let registry: BTreeSet<&str> = currencies.iter().map(|c| c.isocode.as_str()).collect();
+    let rates: BTreeSet<&str> = latest_rates.iter().map(|r| r.isocode.as_str()).collect();
+    Reconciliation {
+        only_in_registry: registry.difference(&rates).map(|s| s.to_string()).collect(),
+        only_in_rates: rates.difference(&registry).map(|s| s.to_string()).collect(),
+        in_both: registry.intersection(&rates).count(),
+    }
 }
 
 /// Represents latest rates data object
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct LatestRate {
     /// The country related to rates data.
     pub country: String,
@@ -340,22 +3324,271 @@ pub struct LatestRate {
     pub isocode: String,
     /// The uic code of the currency.
     #[serde(rename = "uicCode")]
-    pub uiccode: String,
+    pub uiccode: UicCode,
     /// The exchange rate between currency and euro.
-    #[serde(rename = "eurRate")]
+    #[serde(rename = "eurRate", serialize_with = "serialize_decimal")]
     pub eur_rate: Decimal,
     /// The exchange rate between currency and usd.
-    #[serde(rename = "usdRate")]
+    #[cfg(feature = "usd")]
+    #[serde(rename = "usdRate", serialize_with = "serialize_decimal")]
     pub usd_rate: Decimal,
     /// The usd exchange convention.
+    #[cfg(feature = "usd")]
     #[serde(rename = "usdExchangeConvention")]
     pub usd_exchange_convention: String,
     /// The usd exchange convention code.
+    #[cfg(feature = "usd")]
     #[serde(rename = "usdExchangeConventionCode")]
     pub usd_exchange_convention_code: String,
     /// The reference date.
     #[serde(rename = "referenceDate")]
     pub reference_date: Date, //OffsetDateTime,
+    /// The exchange rate between currency and euro as published, when `keep_raw` is enabled.
+    #[serde(
+        rename = "rawEurRate",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub raw_eur_rate: Option<String>,
+    /// The exchange rate between currency and usd as published, when `keep_raw` is enabled.
+    #[cfg(feature = "usd")]
+    #[serde(
+        rename = "rawUsdRate",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub raw_usd_rate: Option<String>,
+}
+
+#[cfg(feature = "chrono")]
+impl LatestRate {
+    /// Returns the reference date as a `chrono::NaiveDate`.
+    pub fn reference_date_chrono(&self) -> chrono::NaiveDate {
+        to_naive_date(self.reference_date)
+    }
+}
+
+impl LatestRate {
+    /// Serializes the latest rate into a JSON string.
+    ///
+    /// `reference_date` is serialized as a `time::Date` value, not as the original API string, so the output must be read
+    /// back with `LatestRate::from_json`.
+    ///
+    /// ## Returns
+    /// - `Ok(String)`: The JSON representation of the latest rate.
+    /// - `Err(BancaDItaliaError)`: If the serialization fails.
+    pub fn to_json(&self) -> Result<String, BancaDItaliaError> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Deserializes a latest rate from a JSON string produced by `LatestRate::to_json`.
+    ///
+    /// ## Arguments
+    /// - `json`: The JSON representation of the latest rate.
+    ///
+    /// ## Returns
+    /// - `Ok(LatestRate)`: The deserialized latest rate.
+    /// - `Err(BancaDItaliaError)`: If the deserialization fails.
+    pub fn from_json(json: &str) -> Result<Self, BancaDItaliaError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Returns the amount of euro corresponding to one unit of the currency.
+    ///
+    /// The function computes the reciprocal of `eur_rate`, rounded to 10 decimal places.
+    ///
+    /// ## Returns
+    /// - `Some(Decimal)`: The euro value of one unit of the currency.
+    /// - `None`: If the rate is unavailable (`N.A.`) or zero.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use bank_of_italy_api::BancaDItalia;
+    ///
+    /// let latest_rates = BancaDItalia::latest_rate_from_file("latest_rates.json").unwrap();
+    /// for rate in latest_rates {
+    ///     println!("{}: {:?}", rate.isocode, rate.eur_per_unit());
+    /// }
+    /// ```
+    pub fn eur_per_unit(&self) -> Option<Decimal> {
+        if self.eur_rate.is_zero() {
+            return None;
+        }
+        Decimal::ONE
+            .checked_div(self.eur_rate)
+            .map(|rate| rate.round_dp(10))
+    }
+
+    /// Returns the convention used to quote `usd_rate`.
+    ///
+    /// ## Returns
+    /// - `Some(UsdExchangeConvention)`: The convention matching `usd_exchange_convention_code`.
+    /// - `None`: If the convention code is not known by the crate.
+    #[cfg(feature = "usd")]
+    pub fn usd_convention(&self) -> Option<UsdExchangeConvention> {
+        UsdExchangeConvention::from_code(&self.usd_exchange_convention_code)
+    }
+
+    /// Returns the units of currency corresponding to one US dollar.
+    ///
+    /// The function normalizes `usd_rate` according to its exchange convention. Rates quoted as US dollars per unit of
+    /// currency are inverted and rounded to 10 decimal places.
+    ///
+    /// ## Returns
+    /// - `Some(Decimal)`: The units of currency for one US dollar.
+    /// - `None`: If the rate is unavailable, zero or quoted with an unknown convention.
+    #[cfg(feature = "usd")]
+    fn units_per_usd(&self) -> Option<Decimal> {
+        if self.usd_rate.is_zero() {
+            return None;
+        }
+        match self.usd_convention()? {
+            UsdExchangeConvention::CurrencyPerDollar => Some(self.usd_rate),
+            UsdExchangeConvention::DollarsPerCurrency => Decimal::ONE
+                .checked_div(self.usd_rate)
+                .map(|rate| rate.round_dp(10)),
+        }
+    }
+
+    /// Returns the rates of the currency as explicit base/quote pairs.
+    ///
+    /// The function emits `EUR/<iso>` from `eur_rate` and `USD/<iso>` from `usd_rate` normalized to units of currency per
+    /// US dollar, so the base of every rate is explicit. The `USD/<iso>` pair carries the convention `usd_rate` was
+    /// published with and is only emitted when the `usd` feature is enabled.
+    ///
+    /// ## Returns
+    /// - `Vec<RatePair>`: The available pairs; unavailable rates are skipped.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use bank_of_italy_api::BancaDItalia;
+    ///
+    /// let latest_rates = BancaDItalia::latest_rate_from_file("latest_rates.json").unwrap();
+    /// for pair in latest_rates.iter().flat_map(|rate| rate.as_pairs()) {
+    ///     println!("{}/{}: {}", pair.base, pair.quote, pair.rate);
+    /// }
+    /// ```
+    pub fn as_pairs(&self) -> Vec<RatePair> {
+        let eur_rate = (!self.eur_rate.is_zero()).then_some(self.eur_rate);
+        #[cfg_attr(not(feature = "usd"), allow(unused_mut))]
+        let mut pairs = vec![("EUR", eur_rate, None)];
+        #[cfg(feature = "usd")]
+        pairs.push(("USD", self.units_per_usd(), self.usd_convention()));
+        pairs
+            .into_iter()
+            .filter_map(|(base, rate, convention)| {
+                Some(RatePair {
+                    base: base.to_string(),
+                    quote: self.isocode.clone(),
+                    rate: rate?,
+                    convention,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Represents a rate between two currencies with explicit base and quote.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RatePair {
+    /// The iso code of the base currency.
+    pub base: String,
+    /// The iso code of the quote currency.
+    pub quote: String,
+    /// The units of `quote` corresponding to one unit of `base`.
+    pub rate: Decimal,
+    /// The convention the US dollar rate was published with, `None` for euro pairs.
+    ///
+    /// `rate` is always expressed as units of `quote` per unit of `base`; the convention tells whether Banca d'Italia
+    /// published it that way (`CurrencyPerDollar`) or as its reciprocal (`DollarsPerCurrency`).
+    pub convention: Option<UsdExchangeConvention>,
+}
+
+/// Represents the convention used by Banca d'Italia to quote the usd rate of a currency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum UsdExchangeConvention {
+    /// The rate is the quantity of currency for 1 US dollar (code `C`).
+    CurrencyPerDollar,
+    /// The rate is the quantity of US dollars for 1 unit of currency (code `I`).
+    DollarsPerCurrency,
+}
+
+impl UsdExchangeConvention {
+    /// Maps a `usdExchangeConventionCode` value to its convention.
+    ///
+    /// ## Arguments
+    /// - `code`: The convention code returned by the API.
+    ///
+    /// ## Returns
+    /// - `Some(UsdExchangeConvention)`: The convention matching the code.
+    /// - `None`: If the code is not known by the crate.
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.trim() {
+            "C" => Some(UsdExchangeConvention::CurrencyPerDollar),
+            "I" => Some(UsdExchangeConvention::DollarsPerCurrency),
+            _ => None,
+        }
+    }
+}
+
+/// Represents the UIC code of a currency, the numeric code assigned by the former Ufficio Italiano dei Cambi.
+///
+/// The code is numeric for all standard entries and is parsed once when the rate is built; non-numeric legacy values
+/// are kept as published. It is serialized as the original string, like the API field.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub struct UicCode {
+    /// The code as published.
+    raw: String,
+    /// The numeric value of the code, if any.
+    numeric: Option<u32>,
+}
+
+impl UicCode {
+    /// Returns the code as published.
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// Returns the numeric value of the code.
+    ///
+    /// ## Returns
+    /// - `Some(u32)`: The numeric value of the code.
+    /// - `None`: If the code is not numeric.
+    pub fn as_u32(&self) -> Option<u32> {
+        self.numeric
+    }
+}
+
+impl From<String> for UicCode {
+    fn from(raw: String) -> Self {
+        let numeric = raw.trim().parse().ok();
+        UicCode { raw, numeric }
+    }
+}
+
+impl From<UicCode> for String {
+    fn from(code: UicCode) -> Self {
+        code.raw
+    }
+}
+
+impl std::fmt::Display for UicCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+impl PartialEq<str> for UicCode {
+    fn eq(&self, other: &str) -> bool {
+        self.raw == other
+    }
+}
+
+impl PartialEq<&str> for UicCode {
+    fn eq(&self, other: &&str) -> bool {
+        self.raw == *other
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -370,16 +3603,19 @@ pub struct LatestRateAPI {
     /// The uic code of the currency.
     #[serde(rename = "uicCode")]
     pub uiccode: String,
-    /// The exchange rate between currency and euro.
-    #[serde(rename = "eurRate")]
+    /// The exchange rate between currency and euro, as a JSON string or number.
+    #[serde(rename = "eurRate", deserialize_with = "string_or_number")]
     pub eur_rate: String,
-    /// The exchange rate between currency and usd.
-    #[serde(rename = "usdRate")]
+    /// The exchange rate between currency and usd, as a JSON string or number.
+    #[cfg(feature = "usd")]
+    #[serde(rename = "usdRate", deserialize_with = "string_or_number")]
     pub usd_rate: String,
     /// The usd exchange convention.
+    #[cfg(feature = "usd")]
     #[serde(rename = "usdExchangeConvention")]
     pub usd_exchange_convention: String,
     /// The usd exchange convention code.
+    #[cfg(feature = "usd")]
     #[serde(rename = "usdExchangeConventionCode")]
     pub usd_exchange_convention_code: String,
     /// The reference date.
@@ -387,53 +3623,840 @@ pub struct LatestRateAPI {
     pub reference_date: String,
 }
 
+/// Represents a quote between two currencies.
+///
+/// Banca d'Italia publishes reference rates only, so bid == ask == mid == `rate`.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Quote {
+    /// The iso code of the currency converted from.
+    pub from: String,
+    /// The iso code of the currency converted to.
+    pub to: String,
+    /// The units of `to` corresponding to one unit of `from`.
+    pub rate: Decimal,
+    /// The reference date of the oldest rate used in the computation.
+    pub reference_date: Date,
+    /// The source of the rates.
+    pub source: String,
+}
+
+#[cfg(feature = "chrono")]
+impl Quote {
+    /// Returns the reference date as a `chrono::NaiveDate`.
+    pub fn reference_date_chrono(&self) -> chrono::NaiveDate {
+        to_naive_date(self.reference_date)
+    }
+}
+
+/// Collects the display names of the currencies, preferring the registry over the latest rates.
+///
+/// ## Arguments
+/// - `currencies`: The currency registry.
+/// - `latest_rates`: The latest exchange rates.
+///
+/// ## Returns
+/// - `HashMap<String, String>`: The normalized display names, keyed by upper case iso code.
+fn currency_names(currencies: &[Currency], latest_rates: &[LatestRate]) -> HashMap<String, String> {
+    let rates = latest_rates
+        .iter()
+        .map(|rate| (&rate.isocode, &rate.currency));
+    let registry = currencies
+        .iter()
+        .map(|currency| (&currency.isocode, &currency.name));
+    rates
+        .chain(registry)
+        .filter(|(_, name)| !name.trim().is_empty())
+        .map(|(iso, name)| (iso.to_uppercase(), normalize_currency_name(name)))
+        .collect()
+}
+
+/// Normalizes the display name of a currency.
+///
+/// ## Arguments
+/// - `name`: The name as published.
+///
+/// ## Returns
+/// - `String`: The name with single spaces between words, title-cased if it was all caps.
+fn normalize_currency_name(name: &str) -> String {
+    let name = name.split_whitespace().collect::<Vec<&str>>().join(" ");
+    if name.chars().any(char::is_lowercase) {
+        return name;
+    }
+    name.split(' ')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first
+                    .to_uppercase()
+                    .chain(chars.flat_map(char::to_lowercase))
+                    .collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Finds the latest rate of a currency, matching the iso code case-insensitively.
+///
+/// ## Arguments
+/// - `latest_rates`: The latest exchange rates.
+/// - `iso`: The iso code of the currency.
+///
+/// ## Returns
+/// - `Ok(&LatestRate)`: The latest rate of the currency.
+/// - `Err(BancaDItaliaError)`: If the currency is not listed.
+fn find_rate<'a>(
+    latest_rates: &'a [LatestRate],
+    iso: &str,
+) -> Result<&'a LatestRate, BancaDItaliaError> {
+    latest_rates
+        .iter()
+        .find(|rate| rate.isocode.eq_ignore_ascii_case(iso))
+        .ok_or_else(|| BancaDItaliaError::CurrencyNotFound(iso.to_string()))
+}
+
+/// Finds the latest rate of a currency from its name, exactly first and partially otherwise.
+///
+/// ## Arguments
+/// - `latest_rates`: The latest exchange rates.
+/// - `name`: The name of the currency, matched case-insensitively.
+///
+/// ## Returns
+/// - `Ok(LatestRate)`: The latest rate of the only matching currency.
+/// - `Err(BancaDItaliaError)`: If no currency or several currencies match.
+fn find_rate_by_name(
+    latest_rates: Vec<LatestRate>,
+    name: &str,
+) -> Result<LatestRate, BancaDItaliaError> {
+    let query = name.trim().to_lowercase();
+    let (exact, others): (Vec<LatestRate>, Vec<LatestRate>) = latest_rates
+        .into_iter()
+        .partition(|rate| rate.currency.trim().to_lowercase() == query);
+    let mut matches = if exact.is_empty() {
+        others
+            .into_iter()
+            .filter(|rate| {
+                let currency = rate.currency.trim().to_lowercase();
+                !currency.is_empty() && (currency.contains(&query) || query.contains(&currency))
+            })
+            .collect()
+    } else {
+        exact
+    };
+    match matches.len() {
+        0 => Err(BancaDItaliaError::CurrencyNotFound(name.to_string())),
+        1 => Ok(matches.remove(0)),
+        _ => Err(BancaDItaliaError::AmbiguousCurrency {
+            name: name.to_string(),
+            candidates: matches
+                .iter()
+                .map(|rate| format!("{} ({})", rate.currency, rate.isocode))
+                .collect(),
+        }),
+    }
+}
+
+/// Computes the cross rate between two currencies through their euro rates.
+///
+/// ## Arguments
+/// - `latest_rates`: The latest exchange rates.
+/// - `from`: The iso code of the currency to convert from.
+/// - `to`: The iso code of the currency to convert to.
+///
+/// ## Returns
+/// - `Ok((Decimal, Date))`: The units of `to` for one unit of `from`, and the oldest reference date of the two rates.
+/// - `Err(BancaDItaliaError)`: If a currency is not listed or its euro rate is unavailable.
+fn cross_rate(
+    latest_rates: &[LatestRate],
+    from: &str,
+    to: &str,
+) -> Result<(Decimal, Date), BancaDItaliaError> {
+    let from_rate = find_rate(latest_rates, from)?;
+    let to_rate = find_rate(latest_rates, to)?;
+    for leg in [from_rate, to_rate] {
+        if leg.eur_rate.is_zero() {
+            return Err(BancaDItaliaError::RateUnavailable {
+                iso: leg.isocode.clone(),
+                field: "eurRate".to_string(),
+            });
+        }
+    }
+    Ok((
+        to_rate.eur_rate / from_rate.eur_rate,
+        from_rate.reference_date.min(to_rate.reference_date),
+    ))
+}
+
+/// Computes the rate of a currency against a base, using the US dollar leg when the base is `USD`.
+///
+/// ## Arguments
+/// - `latest_rates`: The latest exchange rates.
+/// - `base`: The iso code of the base currency.
+/// - `iso`: The iso code of the quoted currency.
+///
+/// ## Returns
+/// - `Ok(Decimal)`: The units of `iso` for one unit of `base`.
+/// - `Err(BancaDItaliaError)`: If a currency is not listed or a rate needed is unavailable.
+fn base_rate(
+    latest_rates: &[LatestRate],
+    base: &str,
+    iso: &str,
+) -> Result<Decimal, BancaDItaliaError> {
+    #[cfg(feature = "usd")]
+    if base.eq_ignore_ascii_case("USD") {
+        let rate = find_rate(latest_rates, iso)?;
+        return rate
+            .units_per_usd()
+            .ok_or_else(|| BancaDItaliaError::RateUnavailable {
+                iso: rate.isocode.clone(),
+                field: "usdRate".to_string(),
+            });
+    }
+    cross_rate(latest_rates, base, iso).map(|(rate, _)| rate)
+}
+
+/// Computes the cross rate between two currencies through their US dollar rates.
+///
+/// ## Arguments
+/// - `latest_rates`: The latest exchange rates.
+/// - `from`: The iso code of the currency to convert from.
+/// - `to`: The iso code of the currency to convert to.
+///
+/// ## Returns
+/// - `Ok(Decimal)`: The units of `to` for one unit of `from`.
+/// - `Err(BancaDItaliaError)`: If a currency is not listed or its US dollar rate is unavailable.
+#[cfg(feature = "usd")]
+fn usd_cross_rate(
+    latest_rates: &[LatestRate],
+    from: &str,
+    to: &str,
+) -> Result<Decimal, BancaDItaliaError> {
+    let units_per_usd = |iso: &str| {
+        let rate = find_rate(latest_rates, iso)?;
+        rate.units_per_usd()
+            .ok_or_else(|| BancaDItaliaError::RateUnavailable {
+                iso: rate.isocode.clone(),
+                field: "usdRate".to_string(),
+            })
+    };
+    Ok(units_per_usd(to)? / units_per_usd(from)?)
+}
+
 /// Converts the metest rates method's results to use date instead of string.
 ///
-/// The function converts the `LatestRateAPI` struct into a `LatestRate` struct so it uses date instead of string.
+/// The function converts the `LatestRateAPI` struct into a `LatestRate` struct so it uses date instead of string. The
+/// reference date is parsed as the start of its day in UTC and only its calendar day is kept.
 ///
 /// ## Arguments
 /// - `latest_rates`: The vector resulting after fetching data from Banca d'Italia API.  
+/// - `strict_rates`: Whether unavailable (`N.A.`) rates are reported as errors instead of being parsed as zero.
+/// - `keep_raw`: Whether the original string rates are kept alongside the parsed ones.
 ///
 /// ## Returns
 /// - `Ok(Vec<LatestRateAPI>)`: A vector containing the latest rates data.
 /// - `Err(BancaDItaliaError)`: If the data fetching fails.
 fn parse_latest_rates(
     latest_rates: Vec<LatestRateAPI>,
+    strict_rates: bool,
+    keep_raw: bool,
 ) -> Result<Vec<LatestRate>, BancaDItaliaError> {
     latest_rates
         .into_iter()
         .map(|rate| {
             let reference_date =
                 parse_to_datetime(&rate.reference_date, DateType::Start, OffsetType::Utc)?.date();
+            let eur_rate = clean_rate(&rate.isocode, "eurRate", &rate.eur_rate, strict_rates)?;
+            #[cfg(feature = "usd")]
+            let usd_rate = clean_rate(&rate.isocode, "usdRate", &rate.usd_rate, strict_rates)?;
             Ok(LatestRate {
                 country: rate.country,
                 currency: rate.currency,
                 isocode: rate.isocode,
-                uiccode: rate.uiccode,
-                eur_rate: clean_decimal(&rate.eur_rate)?,
-                usd_rate: clean_decimal(&rate.usd_rate)?,
+                uiccode: UicCode::from(rate.uiccode),
+                eur_rate,
+                #[cfg(feature = "usd")]
+                usd_rate,
+                #[cfg(feature = "usd")]
                 usd_exchange_convention: rate.usd_exchange_convention,
+                #[cfg(feature = "usd")]
                 usd_exchange_convention_code: rate.usd_exchange_convention_code,
                 reference_date,
+                raw_eur_rate: keep_raw.then_some(rate.eur_rate),
+                #[cfg(feature = "usd")]
+                raw_usd_rate: keep_raw.then_some(rate.usd_rate),
             })
         })
         .collect()
 }
 
+/// Converts a `time::Date` into a `chrono::NaiveDate`.
+///
+/// ## Arguments
+/// - `date`: The date to convert.
+///
+/// ## Returns
+/// - `chrono::NaiveDate`: The same calendar day.
+#[cfg(feature = "chrono")]
+fn to_naive_date(date: Date) -> chrono::NaiveDate {
+    chrono::NaiveDate::from_ymd_opt(
+        date.year(),
+        u8::from(date.month()).into(),
+        date.day().into(),
+    )
+    .expect("time::Date range fits within chrono::NaiveDate range")
+}
+
+/// Converts a rate of a currency, optionally rejecting unavailable values.
+///
+/// ## Arguments
+/// - `iso`: The isocode of the currency, reported on failure.
+/// - `field`: The name of the rate field, reported on failure.
+/// - `input`: The String type rate.
+/// - `strict_rates`: Whether an unavailable (`N.A.`) rate is an error instead of zero.
+///
+/// ## Returns
+/// - `Ok(Decimal)`: The converted rate, zero if unavailable and not strict.
+/// - `Err(BancaDItaliaError)`: If the conversion fails or the rate is unavailable in strict mode.
+fn clean_rate(
+    iso: &str,
+    field: &str,
+    input: &str,
+    strict_rates: bool,
+) -> Result<Decimal, BancaDItaliaError> {
+    if strict_rates && input.trim() == "N.A." {
+        return Err(BancaDItaliaError::RateUnavailable {
+            iso: iso.to_string(),
+            field: field.to_string(),
+        });
+    }
+    clean_decimal(field, input)
+}
+
 /// Clean the response `String` value to correctly convert it into a `rust_decimal::Decimal`.
 ///
 /// The function converts a `String` input into a `Decimal` number.
 ///
 /// ## Arguments
+/// - `field`: The name of the field being converted, reported on failure.
 /// - `input`: The String type number.
 ///
 /// ## Returns
 /// - `Ok(Decimal)`: The converted `Decimal` number.
 /// - `Err(BancaDItaliaError)`: If the conversion fails.
-fn clean_decimal(input: &str) -> Result<Decimal, BancaDItaliaError> {
+fn clean_decimal(field: &str, input: &str) -> Result<Decimal, BancaDItaliaError> {
     let cleaned = input.trim();
     if cleaned == "N.A." {
         return Ok(Decimal::from(0));
     }
-    Ok(Decimal::from_str(cleaned)?)
+    Decimal::from_str(cleaned).map_err(|_| BancaDItaliaError::DecimalParse {
+        field: field.to_string(),
+        value: input.to_string(),
+    })
+}
+
+/// Serializes a `Decimal` as a plain decimal string preserving its scale.
+///
+/// Rates are emitted exactly as they were parsed, e.g. `"77.2500"` rather than `77.25` or an exponent notation, so the
+/// JSON output matches the source format whatever the `rust_decimal` serde configuration.
+///
+/// ## Arguments
+/// - `value`: The decimal to serialize.
+/// - `serializer`: The serializer of the field.
+///
+/// ## Returns
+/// - `Ok(S::Ok)`: The serialized value.
+/// - `Err(S::Error)`: If the serialization fails.
+fn serialize_decimal<S: Serializer>(value: &Decimal, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+/// Deserializes a value given either as a JSON string or as a JSON number into a `String`.
+///
+/// The API returns rates as strings, but numeric values are accepted too so that a change of field type upstream does
+/// not break parsing: numbers are formatted back to their decimal representation and then go through `clean_decimal`
+/// like any other rate.
+///
+/// ## Arguments
+/// - `deserializer`: The deserializer of the field.
+///
+/// ## Returns
+/// - `Ok(String)`: The value as a string.
+/// - `Err(D::Error)`: If the value is neither a string nor a number.
+fn string_or_number<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    struct StringOrNumber;
+
+    impl Visitor<'_> for StringOrNumber {
+        type Value = String;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("a string or a number")
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<String, E> {
+            Ok(value.to_string())
+        }
+
+        fn visit_string<E: de::Error>(self, value: String) -> Result<String, E> {
+            Ok(value)
+        }
+
+        fn visit_i64<E: de::Error>(self, value: i64) -> Result<String, E> {
+            Ok(value.to_string())
+        }
+
+        fn visit_u64<E: de::Error>(self, value: u64) -> Result<String, E> {
+            Ok(value.to_string())
+        }
+
+        fn visit_f64<E: de::Error>(self, value: f64) -> Result<String, E> {
+            Ok(value.to_string())
+        }
+    }
+
+    deserializer.deserialize_any(StringOrNumber)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn country_api(country: &str, start: &str, end: Option<&str>) -> CountryAPI {
+        CountryAPI {
+            currencyiso: "ITL".to_string(),
+            country: country.to_string(),
+            countryiso: None,
+            validity_start_date: start.to_string(),
+            validity_end_date: end.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_parse_currency_skips_inverted_validity_window() {
+        let currencies = vec![CurrencyAPI {
+            countries: vec![
+                country_api("ITALY", "1861-03-17", Some("2002-02-28")),
+                country_api("SAN MARINO", "2002-02-28", Some("1861-03-17")),
+            ],
+            isocode: "ITL".to_string(),
+            name: "Italian Lira".to_string(),
+            graph: false,
+        }];
+        let result = parse_currency(currencies).unwrap();
+        assert_eq!(result[0].countries.len(), 1);
+        assert_eq!(result[0].countries[0].country, "ITALY");
+    }
+
+    #[test]
+    fn test_parse_currency_handles_empty_dates() {
+        let currencies = vec![CurrencyAPI {
+            countries: vec![
+                country_api("ITALY", "1861-03-17", Some("")),
+                country_api("SAN MARINO", "", Some("2002-02-28")),
+            ],
+            isocode: "ITL".to_string(),
+            name: "Italian Lira".to_string(),
+            graph: false,
+        }];
+        let result = parse_currency(currencies).unwrap();
+        assert_eq!(result[0].countries.len(), 1);
+        assert_eq!(result[0].countries[0].country, "ITALY");
+        assert_eq!(result[0].countries[0].validity_end_date, None);
+    }
+
+    #[test]
+    fn test_parse_currency_keeps_boundary_days() {
+        let currencies = vec![CurrencyAPI {
+            countries: vec![
+                country_api("ITALY", "1999-01-01", Some("2001-12-31")),
+                country_api("SAN MARINO", "2000-02-29", Some("2000-02-29")),
+            ],
+            isocode: "ITL".to_string(),
+            name: "Italian Lira".to_string(),
+            graph: false,
+        }];
+        let result = parse_currency(currencies).unwrap();
+        let countries = &result[0].countries;
+        assert_eq!(
+            countries[0].validity_start_date,
+            time::macros::date!(1999 - 01 - 01)
+        );
+        assert_eq!(
+            countries[0].validity_end_date,
+            Some(time::macros::date!(2001 - 12 - 31))
+        );
+        assert_eq!(
+            countries[1].validity_start_date,
+            time::macros::date!(2000 - 02 - 29)
+        );
+        assert_eq!(
+            countries[1].validity_end_date,
+            Some(time::macros::date!(2000 - 02 - 29))
+        );
+    }
+
+    #[test]
+    fn test_parse_currency_handles_missing_countries() {
+        let currencies: Vec<CurrencyAPI> =
+            serde_json::from_str(r#"[{"isoCode": "XAU", "name": "Gold", "graph": false}]"#)
+                .unwrap();
+        let result = parse_currency(currencies).unwrap();
+        assert_eq!(result[0].isocode, "XAU");
+        assert!(result[0].countries.is_empty());
+    }
+
+    #[test]
+    fn test_parse_currency_handles_missing_graph() {
+        let currencies: Vec<CurrencyAPI> = serde_json::from_str(
+            r#"[{"countries": [], "isoCode": "XAU", "name": "Gold"},
+                {"countries": [], "isoCode": "XAG", "name": "Silver", "graph": true}]"#,
+        )
+        .unwrap();
+        let result = parse_currency(currencies).unwrap();
+        assert!(!result[0].graph);
+        assert!(result[1].graph);
+    }
+
+    fn fixture_rate(iso: &str) -> LatestRate {
+        BancaDItalia::latest_rate_from_file(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/latest_rates.json"
+        ))
+        .unwrap()
+        .into_iter()
+        .find(|rate| rate.isocode == iso)
+        .unwrap()
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "test-util")]
+    async fn test_get_latest_rate_or_stale_serves_snapshot() {
+        let transport = MockTransport::new()
+            .with_fixture(
+                "latestRates",
+                concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/tests/fixtures/latest_rates.json"
+                ),
+            )
+            .unwrap();
+        let boi = BancaDItalia::builder()
+            .serve_stale_on_error(true)
+            .build_with_mock(transport);
+        let fresh = boi.get_latest_rate_or_stale().await.unwrap();
+        assert!(!fresh.is_stale());
+
+        let failing = BancaDItalia {
+            client: HttpClient::Mock(Arc::new(MockTransport::new())),
+            ..boi.clone()
+        };
+        let stale = failing.get_latest_rate_or_stale().await.unwrap();
+        assert!(stale.is_stale());
+        assert_eq!(stale.into_inner(), fresh.into_inner());
+
+        let disabled = BancaDItalia {
+            serve_stale_on_error: false,
+            ..failing
+        };
+        assert!(disabled.get_latest_rate_or_stale().await.is_err());
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "test-util")]
+    async fn test_get_latest_rate_or_stale_errors_without_snapshot() {
+        let boi = BancaDItalia::builder()
+            .serve_stale_on_error(true)
+            .build_with_mock(MockTransport::new());
+        assert!(boi.get_latest_rate_or_stale().await.is_err());
+    }
+
+    #[test]
+    fn test_endpoint_urls_render_through_query_builder() {
+        let base_url = "https://example.com/api";
+        assert_eq!(
+            currencies_url!(base_url),
+            "https://example.com/api/currencies?lang=en"
+        );
+        assert_eq!(
+            latestrate_url!(base_url),
+            "https://example.com/api/latestRates?lang=en"
+        );
+    }
+
+    #[test]
+    fn test_parse_latest_rates_keeps_raw() {
+        let payload = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/latest_rates.json"
+        ))
+        .unwrap();
+        let rates = parse_latest_rates(extract_data(&payload, "latestRates").unwrap(), false, true)
+            .unwrap();
+        let zwl = rates.iter().find(|rate| rate.isocode == "ZWL").unwrap();
+        assert_eq!(zwl.raw_eur_rate.as_deref(), Some("N.A."));
+        assert!(zwl.eur_rate.is_zero());
+        assert_eq!(fixture_rate("GBP").raw_eur_rate, None);
+    }
+
+    #[test]
+    fn test_parse_latest_rates_keeps_reference_day() {
+        let rates: Vec<LatestRateAPI> = serde_json::from_str(
+            r#"[
+                {"country": "UNITED KINGDOM", "currency": "Pound Sterling", "isoCode": "GBP",
+                 "uicCode": "2", "eurRate": "0.86753", "usdRate": "1.3409",
+                 "usdExchangeConvention": "Quantity of Dollars for 1 unit of currency",
+                 "usdExchangeConventionCode": "I", "referenceDate": "2024-02-29"},
+                {"country": "JAPAN", "currency": "Yen", "isoCode": "JPY",
+                 "uicCode": "71", "eurRate": "171.28", "usdRate": "147",
+                 "usdExchangeConvention": "Quantity of currency for 1 Dollar",
+                 "usdExchangeConventionCode": "C", "referenceDate": "2024-12-31"}
+            ]"#,
+        )
+        .unwrap();
+        let rates = parse_latest_rates(rates, false, false).unwrap();
+        assert_eq!(rates[0].reference_date, time::macros::date!(2024 - 02 - 29));
+        assert_eq!(rates[1].reference_date, time::macros::date!(2024 - 12 - 31));
+    }
+
+    #[test]
+    fn test_parse_latest_rates_accepts_strings_and_numbers() {
+        let rates: Vec<LatestRateAPI> = serde_json::from_str(
+            r#"[
+                {"country": "UNITED KINGDOM", "currency": "Pound Sterling", "isoCode": "GBP",
+                 "uicCode": "2", "eurRate": 0.86753, "usdRate": "1.3409",
+                 "usdExchangeConvention": "Quantity of Dollars for 1 unit of currency",
+                 "usdExchangeConventionCode": "I", "referenceDate": "2025-07-30"},
+                {"country": "JAPAN", "currency": "Yen", "isoCode": "JPY",
+                 "uicCode": "71", "eurRate": "171.28", "usdRate": 147,
+                 "usdExchangeConvention": "Quantity of currency for 1 Dollar",
+                 "usdExchangeConventionCode": "C", "referenceDate": "2025-07-30"}
+            ]"#,
+        )
+        .unwrap();
+        let rates = parse_latest_rates(rates, false, false).unwrap();
+        assert_eq!(rates[0].eur_rate, Decimal::new(86753, 5));
+        #[cfg(feature = "usd")]
+        assert_eq!(rates[0].usd_rate, Decimal::new(13409, 4));
+        assert_eq!(rates[1].eur_rate, Decimal::new(17128, 2));
+        #[cfg(feature = "usd")]
+        assert_eq!(rates[1].usd_rate, Decimal::from(147));
+
+        let invalid = r#"{"country": "", "currency": "", "isoCode": "", "uicCode": "",
+            "eurRate": true, "usdRate": "1", "usdExchangeConvention": "",
+            "usdExchangeConventionCode": "", "referenceDate": ""}"#;
+        assert!(serde_json::from_str::<LatestRateAPI>(invalid).is_err());
+    }
+
+    #[test]
+    fn test_next_poll_delay_follows_schedule() {
+        use time::macros::{date, datetime};
+
+        let schedule = PublicationSchedule::default();
+        let minutes = |m: u64| Duration::from_secs(m * 60);
+        // Wednesday morning: wait for the window to open.
+        let delay = next_poll_delay(datetime!(2025-07-30 10:00 +1), None, &schedule);
+        assert_eq!(delay, minutes(210));
+        // Within the window, rates of the day still missing: poll at the interval.
+        let delay = next_poll_delay(
+            datetime!(2025-07-30 15:00 +1),
+            Some(date!(2025 - 07 - 29)),
+            &schedule,
+        );
+        assert_eq!(delay, schedule.interval);
+        // Rates of the day published: wait for the next window.
+        let delay = next_poll_delay(
+            datetime!(2025-07-30 15:00 +1),
+            Some(date!(2025 - 07 - 30)),
+            &schedule,
+        );
+        assert_eq!(delay, minutes(22 * 60 + 30));
+        // Friday after the window: skip the weekend.
+        let delay = next_poll_delay(
+            datetime!(2025-08-01 18:00 +1),
+            Some(date!(2025 - 07 - 31)),
+            &schedule,
+        );
+        assert_eq!(delay, minutes((2 * 24 + 19) * 60 + 30));
+        // The current time is converted to the offset of the schedule.
+        let delay = next_poll_delay(datetime!(2025-07-30 14:00 UTC), None, &schedule);
+        assert_eq!(delay, schedule.interval);
+    }
+
+    #[test]
+    fn test_normalize_currency_name() {
+        assert_eq!(normalize_currency_name("EURO"), "Euro");
+        assert_eq!(
+            normalize_currency_name("  POUND   STERLING "),
+            "Pound Sterling"
+        );
+        assert_eq!(normalize_currency_name("Yuan Renminbi"), "Yuan Renminbi");
+        assert_eq!(normalize_currency_name("US Dollar"), "US Dollar");
+    }
+
+    #[test]
+    fn test_currency_names_prefer_registry() {
+        let mut gbp = fixture_rate("GBP");
+        gbp.currency = "POUND STERLING".to_string();
+        let rates = vec![fixture_rate("EUR"), gbp];
+        let currencies = vec![Currency {
+            countries: Vec::new(),
+            isocode: "EUR".to_string(),
+            name: "Euro".to_string(),
+            graph: true,
+        }];
+        let names = currency_names(&currencies, &rates);
+        assert_eq!(names["EUR"], "Euro");
+        assert_eq!(names["GBP"], "Pound Sterling");
+    }
+
+    #[test]
+    fn test_check_known_fields() {
+        let payload = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/currencies.json"
+        ))
+        .unwrap();
+        assert!(check_known_fields(&payload, "currencies").is_ok());
+
+        let payload = br#"{"currencies": [{"countries": [{"currencyISO": "EUR", "region": "EU"}],
+            "isoCode": "EUR", "name": "Euro", "graph": true}]}"#;
+        assert!(matches!(
+            check_known_fields(payload, "currencies"),
+            Err(BancaDItaliaError::UnknownField { field }) if field == "currencies.countries.region"
+        ));
+        let payload = br#"{"latestRates": [{"isoCode": "EUR", "eurRate": "1", "symbol": "E"}]}"#;
+        assert!(matches!(
+            check_known_fields(payload, "latestRates"),
+            Err(BancaDItaliaError::UnknownField { field }) if field == "latestRates.symbol"
+        ));
+    }
+
+    #[test]
+    fn test_find_rate_by_name() {
+        let rates = || {
+            ["AFN", "EUR", "GBP", "JPY", "USD", "ZWL"]
+                .map(fixture_rate)
+                .to_vec()
+        };
+
+        assert_eq!(
+            find_rate_by_name(rates(), "pound sterling")
+                .unwrap()
+                .isocode,
+            "GBP"
+        );
+        assert_eq!(
+            find_rate_by_name(rates(), "Japanese Yen").unwrap().isocode,
+            "JPY"
+        );
+        assert!(matches!(
+            find_rate_by_name(rates(), "Rupee"),
+            Err(BancaDItaliaError::CurrencyNotFound(_))
+        ));
+        assert!(matches!(
+            find_rate_by_name(rates(), "dollar"),
+            Err(BancaDItaliaError::AmbiguousCurrency { candidates, .. }) if candidates.len() == 2
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "usd")]
+    fn test_units_per_usd_follows_convention() {
+        assert_eq!(
+            fixture_rate("JPY").units_per_usd().unwrap().to_string(),
+            "147.61"
+        );
+        assert_eq!(
+            fixture_rate("USD").units_per_usd().unwrap().to_string(),
+            "1"
+        );
+        assert_eq!(
+            fixture_rate("GBP").units_per_usd().unwrap().to_string(),
+            "0.7457677679"
+        );
+        assert_eq!(fixture_rate("ZWL").units_per_usd(), None);
+    }
+
+    #[test]
+    fn test_clean_rate_strict_rejects_unavailable() {
+        assert!(clean_rate("ZWL", "eurRate", "N.A.", false)
+            .unwrap()
+            .is_zero());
+        match clean_rate("ZWL", "eurRate", " N.A. ", true) {
+            Err(BancaDItaliaError::RateUnavailable { iso, field }) => {
+                assert_eq!(iso, "ZWL");
+                assert_eq!(field, "eurRate");
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert_eq!(
+            clean_rate("GBP", "eurRate", "0.86753", true)
+                .unwrap()
+                .to_string(),
+            "0.86753"
+        );
+    }
+
+    #[test]
+    fn test_clean_decimal_reports_field_and_value() {
+        assert_eq!(
+            clean_decimal("eurRate", " 1.1633 ").unwrap().to_string(),
+            "1.1633"
+        );
+        assert!(clean_decimal("usdRate", "N.A.").unwrap().is_zero());
+        match clean_decimal("usdRate", "1,1633") {
+            Err(BancaDItaliaError::DecimalParse { field, value }) => {
+                assert_eq!(field, "usdRate");
+                assert_eq!(value, "1,1633");
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_builder_base_url_takes_precedence() {
+        let boi = BancaDItalia::builder()
+            .base_url("http://localhost:8080/rest/")
+            .build()
+            .unwrap();
+        assert_eq!(boi.base_url, "http://localhost:8080/rest");
+    }
+
+    #[test]
+    fn test_resolve_base_url_precedence() {
+        let url = |value: &str| Some(value.to_string());
+        assert_eq!(
+            resolve_base_url(url("http://builder/rest/"), url("http://env/rest")),
+            "http://builder/rest"
+        );
+        assert_eq!(
+            resolve_base_url(None, url("http://env/rest/")),
+            "http://env/rest"
+        );
+        assert_eq!(resolve_base_url(None, url("")), BOI_BASE_URL);
+        assert_eq!(resolve_base_url(None, None), BOI_BASE_URL);
+    }
+
+    #[test]
+    fn test_builder_base_urls_sets_fallbacks() {
+        let boi = BancaDItalia::builder()
+            .base_urls(vec![
+                "http://primary/rest/".to_string(),
+                "http://mirror/rest/".to_string(),
+            ])
+            .build()
+            .unwrap();
+        assert_eq!(boi.base_url, "http://primary/rest");
+        assert_eq!(boi.fallback_base_urls, vec!["http://mirror/rest"]);
+    }
+
+    #[test]
+    fn test_max_concurrency_sets_permits() {
+        let boi = BancaDItalia::builder().max_concurrency(2).build().unwrap();
+        assert_eq!(boi.limiter.available_permits(), 2);
+        let boi = BancaDItalia::builder().max_concurrency(0).build().unwrap();
+        assert_eq!(boi.limiter.available_permits(), 1);
+        let boi = BancaDItalia::new().unwrap();
+        assert_eq!(boi.limiter.available_permits(), DEFAULT_MAX_CONCURRENCY);
+    }
 }
@@ -0,0 +1,103 @@
+//! # Mock Transport
+//!
+//! This module provides an in-memory transport that answers requests with canned responses instead of contacting the
+//! Banca d'Italia servers, so tests can run offline and deterministically. Requires the `test-util` feature.
+
+use crate::{endpoint_name, BancaDItaliaError, ResponseFormat};
+use bytes::Bytes;
+use reqwest::ResponseBuilderExt;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// An in-memory transport mapping endpoints to canned responses.
+///
+/// Requests are matched on the endpoint name, e.g. `currencies` or `latestRates`, whatever the base url and query
+/// parameters. Endpoints without a registered response are answered with an empty `404 Not Found`.
+///
+/// ## Example
+/// ```rust
+/// use bank_of_italy_api::{BancaDItalia, MockTransport};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let transport = MockTransport::new()
+///         .with_fixture("latestRates", "tests/fixtures/latest_rates.json")
+///         .unwrap();
+///     let boi = BancaDItalia::with_mock(transport);
+///     let latest_rates = boi.get_latest_rate().await.unwrap();
+///     assert_eq!(latest_rates.len(), 6);
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MockTransport {
+    /// The status and body returned for each endpoint.
+    responses: HashMap<String, (u16, Bytes)>,
+}
+
+impl MockTransport {
+    /// Creates a transport without any registered response.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the response returned for an endpoint.
+    ///
+    /// ## Arguments
+    /// - `endpoint`: The endpoint name, e.g. `latestRates`.
+    /// - `status`: The HTTP status code of the response.
+    /// - `body`: The body of the response.
+    pub fn with_response(mut self, endpoint: &str, status: u16, body: impl Into<Bytes>) -> Self {
+        self.responses
+            .insert(endpoint.to_string(), (status, body.into()));
+        self
+    }
+
+    /// Registers a file as the successful response of an endpoint.
+    ///
+    /// ## Arguments
+    /// - `endpoint`: The endpoint name, e.g. `latestRates`.
+    /// - `path`: The path of the file holding the response body.
+    ///
+    /// ## Returns
+    /// - `Ok(Self)`: The transport with the response registered.
+    /// - `Err(BancaDItaliaError)`: If the file cannot be read.
+    pub fn with_fixture(
+        self,
+        endpoint: &str,
+        path: impl AsRef<Path>,
+    ) -> Result<Self, BancaDItaliaError> {
+        let body = std::fs::read(path)?;
+        Ok(self.with_response(endpoint, 200, body))
+    }
+
+    /// Answers a request with the registered response of its endpoint.
+    ///
+    /// ## Arguments
+    /// - `url`: The url of the request.
+    /// - `format`: The format requested through the `Accept` header, echoed as the `Content-Type`.
+    ///
+    /// ## Returns
+    /// - `Ok(reqwest::Response)`: The canned response.
+    /// - `Err(BancaDItaliaError)`: If the url or a registered status code is invalid.
+    pub(crate) fn respond(
+        &self,
+        url: &str,
+        format: ResponseFormat,
+    ) -> Result<reqwest::Response, BancaDItaliaError> {
+        let (status, body) = self
+            .responses
+            .get(endpoint_name(url))
+            .cloned()
+            .unwrap_or((404, Bytes::new()));
+        let response = http::Response::builder()
+            .url(
+                reqwest::Url::parse(url)
+                    .map_err(|err| BancaDItaliaError::ApiError(err.to_string()))?,
+            )
+            .status(status)
+            .header("Content-Type", format.accept())
+            .body(body)
+            .map_err(|err| BancaDItaliaError::ApiError(err.to_string()))?;
+        Ok(reqwest::Response::from(response))
+    }
+}
@@ -0,0 +1,131 @@
+//! # Query Parameters
+//!
+//! This module renders the query strings sent to Banca d'Italia servers. Every key is set through a typed setter, so
+//! the key names and the value formats live in one place instead of being spelled out by each endpoint method.
+
+use std::fmt;
+use time::macros::format_description;
+use time::Date;
+use url::form_urlencoded;
+
+/// A builder rendering the query string of a Banca d'Italia request.
+///
+/// Parameters are rendered in the order they are set, as `key=value` pairs joined by `&`, without the leading `?`.
+/// Values are form-encoded, so a value containing `&`, `=` or spaces cannot break the query apart.
+///
+/// ## Example
+/// ```rust
+/// use bank_of_italy_api::QueryBuilder;
+/// use time::macros::date;
+///
+/// let query = QueryBuilder::new()
+///     .reference_date(date!(2025 - 07 - 30))
+///     .currency("USD")
+///     .lang("en");
+/// assert_eq!(query.render(), "referenceDate=2025-07-30&currencyIsoCode=USD&lang=en");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryBuilder {
+    params: Vec<(&'static str, String)>,
+}
+
+impl QueryBuilder {
+    /// Creates an empty query.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the language of the response, rendered as `lang`.
+    pub fn lang(self, lang: &str) -> Self {
+        self.param("lang", lang.to_string())
+    }
+
+    /// Sets the reference date, rendered as `referenceDate` in `YYYY-MM-DD` format.
+    pub fn reference_date(self, date: Date) -> Self {
+        self.param("referenceDate", format_date(date))
+    }
+
+    /// Sets the iso code of the quoted currency, rendered as `currencyIsoCode`.
+    pub fn currency(self, iso: &str) -> Self {
+        self.param("currencyIsoCode", iso.to_uppercase())
+    }
+
+    /// Sets the iso code of the base currency, rendered as `baseCurrencyIsoCode`.
+    pub fn base(self, iso: &str) -> Self {
+        self.param("baseCurrencyIsoCode", iso.to_uppercase())
+    }
+
+    /// Sets an inclusive date range, rendered as `startDate` and `endDate` in `YYYY-MM-DD` format.
+    pub fn date_range(self, start: Date, end: Date) -> Self {
+        self.param("startDate", format_date(start))
+            .param("endDate", format_date(end))
+    }
+
+    /// Renders the query string, without the leading `?`.
+    pub fn render(&self) -> String {
+        self.to_string()
+    }
+
+    /// Appends a parameter to the query.
+    fn param(mut self, key: &'static str, value: String) -> Self {
+        self.params.push((key, value));
+        self
+    }
+}
+
+impl fmt::Display for QueryBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(
+            &form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish(),
+        )
+    }
+}
+
+/// Formats a date as expected by Banca d'Italia servers.
+///
+/// ## Arguments
+/// - `date`: The date to format.
+///
+/// ## Returns
+/// - `String`: The date in `YYYY-MM-DD` format.
+fn format_date(date: Date) -> String {
+    date.format(format_description!("[year]-[month]-[day]"))
+        .expect("a calendar date always formats")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::date;
+
+    #[test]
+    fn test_query_builder_renders_exact_keys() {
+        assert_eq!(QueryBuilder::new().render(), "");
+        assert_eq!(QueryBuilder::new().lang("en").render(), "lang=en");
+        assert_eq!(
+            QueryBuilder::new()
+                .reference_date(date!(2025 - 07 - 03))
+                .base("eur")
+                .currency("usd")
+                .lang("en")
+                .render(),
+            "referenceDate=2025-07-03&baseCurrencyIsoCode=EUR&currencyIsoCode=USD&lang=en"
+        );
+        assert_eq!(
+            QueryBuilder::new()
+                .date_range(date!(2025 - 01 - 01), date!(2025 - 12 - 31))
+                .render(),
+            "startDate=2025-01-01&endDate=2025-12-31"
+        );
+    }
+
+    #[test]
+    fn test_query_builder_encodes_values() {
+        assert_eq!(
+            QueryBuilder::new().lang("en&lang=it it").render(),
+            "lang=en%26lang%3Dit+it"
+        );
+    }
+}
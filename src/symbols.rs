@@ -0,0 +1,69 @@
+//! # Currency Symbols
+//!
+//! This module bundles the symbols of the major currencies, which the Banca d'Italia API does not provide. Coverage is
+//! best-effort: currencies without a widely used symbol of their own are not listed.
+
+/// The symbols of the major currencies, sorted by iso code.
+const SYMBOLS: &[(&str, &str)] = &[
+    ("AUD", "A$"),
+    ("BRL", "R$"),
+    ("CAD", "C$"),
+    ("CHF", "CHF"),
+    ("CNY", "¥"),
+    ("CZK", "Kč"),
+    ("DKK", "kr"),
+    ("EUR", "€"),
+    ("GBP", "£"),
+    ("HKD", "HK$"),
+    ("HUF", "Ft"),
+    ("ILS", "₪"),
+    ("INR", "₹"),
+    ("JPY", "¥"),
+    ("KRW", "₩"),
+    ("MXN", "MX$"),
+    ("NOK", "kr"),
+    ("NZD", "NZ$"),
+    ("PHP", "₱"),
+    ("PLN", "zł"),
+    ("RUB", "₽"),
+    ("SEK", "kr"),
+    ("SGD", "S$"),
+    ("THB", "฿"),
+    ("TRY", "₺"),
+    ("UAH", "₴"),
+    ("USD", "$"),
+    ("VND", "₫"),
+    ("ZAR", "R"),
+];
+
+/// Finds the symbol of a currency.
+///
+/// ## Arguments
+/// - `iso`: The iso code of the currency, matched case-insensitively.
+///
+/// ## Returns
+/// - `Some(&'static str)`: The symbol of the currency.
+/// - `None`: If the currency is not in the bundled table.
+pub(crate) fn symbol_for(iso: &str) -> Option<&'static str> {
+    let iso = iso.trim().to_ascii_uppercase();
+    SYMBOLS
+        .binary_search_by(|(code, _)| (*code).cmp(iso.as_str()))
+        .ok()
+        .map(|index| SYMBOLS[index].1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_symbols_are_sorted() {
+        assert!(SYMBOLS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn test_symbol_for() {
+        assert_eq!(symbol_for("eur"), Some("€"));
+        assert_eq!(symbol_for("ITL"), None);
+    }
+}
@@ -0,0 +1,39 @@
+//! # Argument Validation
+//!
+//! This module checks the arguments of the public methods before any request is sent, so invalid input fails fast
+//! with `BancaDItaliaError::InvalidArgument` instead of surfacing later as a confusing lookup or API error.
+
+use crate::BancaDItaliaError;
+
+/// Checks that a text argument, such as an iso code or a country, is not blank.
+///
+/// ## Arguments
+/// - `name`: The name of the argument, reported in the error.
+/// - `value`: The value of the argument.
+///
+/// ## Returns
+/// - `Ok(())`: If the value contains non-whitespace characters.
+/// - `Err(BancaDItaliaError)`: If the value is empty or only whitespace.
+pub(crate) fn non_empty(name: &str, value: &str) -> Result<(), BancaDItaliaError> {
+    if value.trim().is_empty() {
+        return Err(BancaDItaliaError::InvalidArgument {
+            name: name.to_string(),
+            reason: "must not be empty".to_string(),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_non_empty_rejects_blank_values() {
+        assert!(non_empty("iso", "USD").is_ok());
+        assert!(matches!(
+            non_empty("iso", "  "),
+            Err(BancaDItaliaError::InvalidArgument { name, .. }) if name == "iso"
+        ));
+    }
+}
@@ -1,26 +1,120 @@
-use bank_of_italy_api::BancaDItalia;
+use bank_of_italy_api::{
+    BancaDItalia, BancaDItaliaError, Country, Currency, Endpoint, EndpointOutcome, FixedClock,
+    FormattedPayload, LatestRate, MockTransport, Notice, RateAnomalyKind, RatePair, RatesReport,
+    RegistryDiff, RegistryRow, RequestMetrics, ResponseFormat, ResultInfo, UicCode, COUNTRY_FIELDS,
+    CURRENCY_FIELDS, DEFAULT_MAX_PLAUSIBLE_RATE, LATEST_RATE_FIELDS,
+};
+use rust_decimal::Decimal;
+use std::io::{Read, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use time::macros::{date, datetime, offset};
+
+fn mock_client() -> BancaDItalia {
+    BancaDItalia::with_mock(mock_transport())
+}
+
+fn mock_transport() -> MockTransport {
+    MockTransport::new()
+        .with_fixture(
+            "currencies",
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/tests/fixtures/currencies.json"
+            ),
+        )
+        .unwrap()
+        .with_fixture(
+            "latestRates",
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/tests/fixtures/latest_rates.json"
+            ),
+        )
+        .unwrap()
+}
+
+/// Serves the latest rates fixture over HTTP on a local port and returns its base url.
+fn serve_latest_rates() -> String {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let body = std::fs::read(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/latest_rates.json"
+    ))
+    .unwrap();
+    std::thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+            while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                match stream.read(&mut buffer) {
+                    Ok(0) | Err(_) => break,
+                    Ok(read) => request.extend_from_slice(&buffer[..read]),
+                }
+            }
+            let header = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            );
+            let _ = stream.write_all(header.as_bytes());
+            let _ = stream.write_all(&body);
+        }
+    });
+    base_url
+}
+
+/// Returns the base url of a local port nothing listens on.
+fn unreachable_url() -> String {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    format!("http://{}", listener.local_addr().unwrap())
+}
+
+#[tokio::test]
+async fn test_get_currencies_sorts_upstream_order() {
+    let payload = r#"{"currencies": [
+        {"countries": [], "isoCode": "ITL", "name": "Italian Lira", "graph": false},
+        {"countries": [], "isoCode": "ADP", "name": "Andorran Peseta", "graph": false},
+        {"countries": [], "isoCode": "EUR", "name": "Euro", "graph": true}
+    ]}"#;
+    let boi =
+        BancaDItalia::with_mock(MockTransport::new().with_response("currencies", 200, payload));
+    let isocodes = |currencies: Vec<Currency>| -> Vec<String> {
+        currencies.into_iter().map(|c| c.isocode).collect()
+    };
+    assert_eq!(
+        isocodes(boi.get_currencies().await.unwrap()),
+        vec!["ADP", "EUR", "ITL"]
+    );
+    assert_eq!(
+        isocodes(boi.get_currencies_unsorted().await.unwrap()),
+        vec!["ITL", "ADP", "EUR"]
+    );
+}
 
 #[tokio::test]
 async fn test_get_currencies() {
-    let boi = BancaDItalia::new().unwrap();
+    let boi = mock_client();
     let response = boi.get_currencies().await;
-    assert!(response.is_ok());
+    assert!(response.is_ok(), "Error: {:#?}", response);
     let result = response.unwrap();
+    assert!(result.windows(2).all(|w| w[0].isocode <= w[1].isocode));
     assert_eq!(result[0].isocode, "ADP");
     assert_eq!(result[0].countries[0].currencyiso, "ADP");
     assert_eq!(result[0].countries[0].country, "ANDORRA");
 
-    assert_eq!(result[2].isocode, "AFN");
-    assert_eq!(result[2].countries[0].currencyiso, "AFN");
+    assert_eq!(result[1].isocode, "AFN");
+    assert_eq!(result[1].countries[0].currencyiso, "AFN");
     assert_eq!(
-        result[2].countries[0].country,
+        result[1].countries[0].country,
         "AFGHANISTAN (Islamic State of)"
     );
 }
 
 #[tokio::test]
 async fn test_get_latest_rates() {
-    let boi = BancaDItalia::new().unwrap();
+    let boi = mock_client();
     let response = boi.get_latest_rate().await;
     assert!(response.is_ok(), "Error: {:#?}", response);
     let result = response.unwrap();
@@ -33,3 +127,1041 @@ async fn test_get_latest_rates() {
     assert_eq!(euro.currency, "Euro");
     assert_eq!(euro.country, "EUROPEAN MONETARY UNION");
 }
+
+#[test]
+fn test_latest_rate_from_file() {
+    let response = BancaDItalia::latest_rate_from_file(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/latest_rates.json"
+    ));
+    assert!(response.is_ok(), "Error: {:#?}", response);
+    let result = response.unwrap();
+    assert_eq!(result.len(), 6);
+    assert_eq!(result[0].isocode, "AFN");
+    assert_eq!(result[0].eur_rate.to_string(), "80.3844");
+
+    let missing = BancaDItalia::latest_rate_from_file("tests/fixtures/does_not_exist.json");
+    assert!(matches!(missing, Err(BancaDItaliaError::Io(_))));
+}
+
+#[tokio::test]
+async fn test_reconcile() {
+    let boi = mock_client();
+    let response = boi.reconcile().await;
+    assert!(response.is_ok(), "Error: {:#?}", response);
+    let result = response.unwrap();
+    assert!(result.in_both > 0);
+    assert!(!result.only_in_rates.contains(&"EUR".to_string()));
+    assert!(!result.only_in_registry.contains(&"EUR".to_string()));
+}
+
+#[tokio::test]
+async fn test_orphan_codes() {
+    let boi = BancaDItalia::builder()
+        .collect_orphan_codes(true)
+        .build_with_mock(mock_transport());
+    assert!(boi.orphan_codes().is_empty());
+    boi.clone().reconcile().await.unwrap();
+    assert_eq!(boi.orphan_codes(), vec!["GBP", "JPY", "USD", "ZWL"]);
+
+    let disabled = mock_client();
+    disabled.reconcile().await.unwrap();
+    assert!(disabled.orphan_codes().is_empty());
+}
+
+#[tokio::test]
+async fn test_formatted_payloads() {
+    let csv = "isoCode,eurRate\nGBP,0.86753\n";
+    let pdf = b"%PDF-1.4".to_vec();
+    let boi = BancaDItalia::with_mock(
+        mock_transport()
+            .with_response("latestRates", 200, csv)
+            .with_response("currencies", 200, pdf.clone()),
+    );
+    assert_eq!(
+        boi.get_latest_rate_as(ResponseFormat::Csv).await.unwrap(),
+        FormattedPayload::Csv(csv.to_string())
+    );
+    assert_eq!(
+        boi.get_currencies_as(ResponseFormat::Pdf).await.unwrap(),
+        FormattedPayload::Pdf(pdf)
+    );
+
+    match mock_client()
+        .get_currencies_as(ResponseFormat::Json)
+        .await
+        .unwrap()
+    {
+        FormattedPayload::Json(json) => assert!(json["currencies"].is_array()),
+        other => panic!("unexpected payload: {:?}", other),
+    }
+
+    let failing = BancaDItalia::with_mock(
+        MockTransport::new()
+            .with_response("currencies", 406, "Not Acceptable")
+            .with_response("latestRates", 500, "Internal Server Error"),
+    );
+    match failing.get_currencies_as(ResponseFormat::Pdf).await {
+        Err(BancaDItaliaError::ApiError(message)) => assert!(message.contains("406")),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    match failing.get_latest_rate_as(ResponseFormat::Csv).await {
+        Err(BancaDItaliaError::ApiError(message)) => assert!(message.contains("500")),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_endpoints() {
+    let endpoints = BancaDItalia::endpoints();
+    assert_eq!(endpoints.len(), Endpoint::ALL.len());
+    for (info, endpoint) in endpoints.iter().zip(Endpoint::ALL) {
+        assert_eq!(*info, endpoint.info());
+        assert_eq!(info.path, format!("/{}", info.name));
+        assert_eq!(info.required_params, ["lang"]);
+        assert_eq!(info.formats, [ResponseFormat::Json, ResponseFormat::Csv]);
+    }
+    assert_eq!(Endpoint::LatestRates.path(), "/latestRates");
+}
+
+#[tokio::test]
+async fn test_empty_dataset_vs_missing_key() {
+    let boi = BancaDItalia::with_mock(MockTransport::new().with_response(
+        "currencies",
+        200,
+        r#"{"currencies": []}"#,
+    ));
+    assert!(boi.get_currencies().await.unwrap().is_empty());
+
+    let boi = BancaDItalia::with_mock(MockTransport::new().with_response(
+        "currencies",
+        200,
+        r#"{"resultsInfo": {}}"#,
+    ));
+    match boi.get_currencies().await {
+        Err(BancaDItaliaError::MissingKey {
+            key,
+            available_keys,
+        }) => {
+            assert_eq!(key, "currencies");
+            assert_eq!(available_keys, vec!["resultsInfo"]);
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    let boi = BancaDItalia::with_mock(MockTransport::new().with_response(
+        "currencies",
+        200,
+        r#"{"currencies": null}"#,
+    ));
+    assert!(matches!(
+        boi.get_currencies().await,
+        Err(BancaDItaliaError::NoResult)
+    ));
+}
+
+#[test]
+fn test_mapped_fields_present_in_sample_payloads() {
+    let fixture = |name: &str| -> serde_json::Value {
+        let path = format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name);
+        serde_json::from_slice(&std::fs::read(path).unwrap()).unwrap()
+    };
+
+    let latest_rates = fixture("latest_rates.json");
+    for rate in latest_rates["latestRates"].as_array().unwrap() {
+        for field in LATEST_RATE_FIELDS {
+            assert!(rate.get(field).is_some(), "missing field {field}");
+        }
+    }
+
+    let currencies = fixture("currencies.json");
+    for currency in currencies["currencies"].as_array().unwrap() {
+        for field in CURRENCY_FIELDS {
+            assert!(currency.get(field).is_some(), "missing field {field}");
+        }
+        for country in currency["countries"].as_array().unwrap() {
+            for field in COUNTRY_FIELDS {
+                assert!(country.get(field).is_some(), "missing field {field}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_eur_per_unit() {
+    let result = BancaDItalia::latest_rate_from_file(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/latest_rates.json"
+    ))
+    .unwrap();
+    let rate = |iso: &str| result.iter().find(|rate| rate.isocode == iso).unwrap();
+
+    assert_eq!(rate("EUR").eur_per_unit().unwrap().to_string(), "1");
+    assert_eq!(
+        rate("GBP").eur_per_unit().unwrap().to_string(),
+        "1.1526978894"
+    );
+    assert_eq!(rate("ZWL").eur_per_unit(), None);
+}
+
+#[test]
+fn test_result_info_notices() {
+    let info = ResultInfo {
+        total_records: 0,
+        timezone_reference: "Dates refer to the Central European Time Zone".to_string(),
+        notice: "Provisional data; Publication delayed\nRates were not published today; Check back later"
+            .to_string(),
+    };
+    assert_eq!(
+        info.notices(),
+        vec![
+            Notice::ProvisionalData,
+            Notice::Delayed,
+            Notice::NoPublicationToday,
+            Notice::Other("Check back later".to_string()),
+        ]
+    );
+
+    let empty = ResultInfo {
+        notice: String::new(),
+        ..info
+    };
+    assert!(empty.notices().is_empty());
+}
+
+#[tokio::test]
+async fn test_quote() {
+    let boi = mock_client();
+    let response = boi.quote("eur", "EUR").await;
+    assert!(response.is_ok(), "Error: {:#?}", response);
+    let quote = response.unwrap();
+    assert_eq!(quote.from, "EUR");
+    assert_eq!(quote.rate.to_string(), "1");
+    assert_eq!(quote.source, "Banca d'Italia");
+
+    let missing = boi.quote("EUR", "XXX").await;
+    assert!(matches!(
+        missing,
+        Err(BancaDItaliaError::CurrencyNotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_auto_refresh() {
+    let boi = mock_client();
+    assert!(boi.current_snapshot().is_none());
+
+    let handle = boi.spawn_auto_refresh(Duration::from_secs(3600));
+    for _ in 0..50 {
+        if boi.current_snapshot().is_some() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+    handle.stop();
+
+    let snapshot = boi.current_snapshot();
+    assert!(snapshot.is_some());
+    assert!(!snapshot.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_dropping_refresh_handle_stops_requests() {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&requests);
+    let boi = BancaDItalia::builder()
+        .on_request(Box::new(move |_: RequestMetrics| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        }))
+        .build_with_mock(mock_transport());
+
+    let handle = boi.spawn_auto_refresh(Duration::from_millis(20));
+    for _ in 0..100 {
+        if requests.load(Ordering::SeqCst) >= 2 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert!(requests.load(Ordering::SeqCst) >= 2);
+    drop(handle);
+
+    let after_drop = requests.load(Ordering::SeqCst);
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(requests.load(Ordering::SeqCst), after_drop);
+}
+
+#[test]
+fn test_total_records() {
+    let payload = std::fs::read(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/latest_rates.json"
+    ))
+    .unwrap();
+    assert_eq!(BancaDItalia::total_records(&payload).unwrap(), 6);
+    assert!(BancaDItalia::total_records(b"{}").is_err());
+}
+
+#[tokio::test]
+async fn test_snapshot_to_file() {
+    let boi = mock_client();
+    let path = std::env::temp_dir().join("bank_of_italy_api_test_snapshot.json");
+    let response = boi.snapshot_to_file(&path).await;
+    assert!(response.is_ok(), "Error: {:#?}", response);
+
+    let report: RatesReport = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(!report.latest_rates.is_empty());
+    assert!(report.latest_rates.iter().any(|rate| rate.isocode == "EUR"));
+
+    // Renaming onto a non-empty directory fails, which must not leave the temporary file behind.
+    let dir = std::env::temp_dir().join("bank_of_italy_api_test_snapshot_dir");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("keep"), b"").unwrap();
+    let response = boi.snapshot_to_file(&dir).await;
+    let tmp_exists = dir.with_extension("tmp").exists();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(
+        matches!(response, Err(BancaDItaliaError::Io(_))),
+        "Error: {:#?}",
+        response
+    );
+    assert!(!tmp_exists);
+}
+
+#[test]
+fn test_missing_key_lists_available_keys() {
+    let response = BancaDItalia::latest_rate_from_file(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/unexpected_shape.json"
+    ));
+    match response {
+        Err(BancaDItaliaError::MissingKey {
+            key,
+            available_keys,
+        }) => {
+            assert_eq!(key, "latestRates");
+            assert_eq!(available_keys, vec!["rates", "resultsInfo"]);
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[tokio::test]
+#[cfg(feature = "usd")]
+async fn test_distinct_usd_conventions() {
+    let boi = mock_client();
+    let response = boi.distinct_usd_conventions().await;
+    assert!(response.is_ok(), "Error: {:#?}", response);
+    let result = response.unwrap();
+    assert!(!result.is_empty());
+    assert!(result.windows(2).all(|w| w[0] < w[1]));
+}
+
+#[tokio::test]
+async fn test_currencies_starting_with() {
+    let boi = mock_client();
+    let isocodes = |currencies: Vec<Currency>| -> Vec<String> {
+        currencies.into_iter().map(|c| c.isocode).collect()
+    };
+    assert_eq!(
+        isocodes(boi.currencies_starting_with("a").await.unwrap()),
+        vec!["ADP", "AFN"]
+    );
+    assert_eq!(
+        isocodes(boi.currencies_starting_with(" Eu").await.unwrap()),
+        vec!["EUR"]
+    );
+    assert_eq!(boi.currencies_starting_with("").await.unwrap().len(), 4);
+    assert!(boi
+        .currencies_starting_with("XYZ")
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test]
+async fn test_currencies_for_country() {
+    let boi = mock_client();
+    let response = boi.currencies_for_country("ita").await;
+    assert!(response.is_ok(), "Error: {:#?}", response);
+    let result = response.unwrap();
+    assert!(result.iter().any(|currency| currency.isocode == "ITL"));
+
+    let by_name = boi.currencies_for_country("Italy").await.unwrap();
+    assert!(by_name.iter().any(|currency| currency.isocode == "ITL"));
+}
+
+#[test]
+fn test_latest_rate_json_round_trip() {
+    let result = BancaDItalia::latest_rate_from_file(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/latest_rates.json"
+    ))
+    .unwrap();
+    for rate in result {
+        let json = rate.to_json().unwrap();
+        assert_eq!(LatestRate::from_json(&json).unwrap(), rate);
+    }
+}
+
+#[test]
+fn test_currency_json_round_trip() {
+    let currency = Currency {
+        countries: vec![Country {
+            currencyiso: "ITL".to_string(),
+            country: "ITALY".to_string(),
+            countryiso: Some("ITA".to_string()),
+            validity_start_date: date!(1861 - 03 - 17),
+            validity_end_date: Some(date!(2002 - 02 - 28)),
+        }],
+        isocode: "ITL".to_string(),
+        name: "Italian Lira".to_string(),
+        graph: false,
+    };
+    let json = currency.to_json().unwrap();
+    assert!(json.contains("\"isoCode\":\"ITL\""));
+    assert_eq!(Currency::from_json(&json).unwrap(), currency);
+}
+
+#[tokio::test]
+async fn test_builder_pool_options() {
+    let boi = BancaDItalia::builder()
+        .pool_idle_timeout(Duration::from_secs(300))
+        .pool_max_idle_per_host(1)
+        .base_url(serve_latest_rates())
+        .build()
+        .unwrap();
+    let response = boi.get_latest_rate().await;
+    assert!(response.is_ok(), "Error: {:#?}", response);
+}
+
+#[test]
+fn test_find_rate_anomalies() {
+    let mut result = BancaDItalia::latest_rate_from_file(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/latest_rates.json"
+    ))
+    .unwrap();
+    assert!(BancaDItalia::find_rate_anomalies(&result, DEFAULT_MAX_PLAUSIBLE_RATE).is_empty());
+
+    result[1].eur_rate = Decimal::NEGATIVE_ONE;
+    let anomalies = BancaDItalia::find_rate_anomalies(&result, Decimal::from(150));
+    assert_eq!(anomalies.len(), 2);
+    assert_eq!(anomalies[0].isocode, "EUR");
+    assert_eq!(anomalies[0].field, "eurRate");
+    assert_eq!(anomalies[0].kind, RateAnomalyKind::Negative);
+    assert_eq!(anomalies[1].isocode, "JPY");
+    assert_eq!(anomalies[1].field, "eurRate");
+    assert_eq!(anomalies[1].kind, RateAnomalyKind::AboveThreshold);
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_chrono_accessors() {
+    let result = BancaDItalia::latest_rate_from_file(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/latest_rates.json"
+    ))
+    .unwrap();
+    assert_eq!(
+        result[0].reference_date_chrono(),
+        chrono::NaiveDate::from_ymd_opt(2025, 7, 30).unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_resolve_currency_name_refetches_on_miss() {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&requests);
+    let boi = BancaDItalia::builder()
+        .on_request(Box::new(move |_: RequestMetrics| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        }))
+        .build_with_mock(mock_transport());
+
+    assert_eq!(boi.resolve_currency_name("eur").await.unwrap(), "Euro");
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+    assert_eq!(boi.resolve_currency_name("EUR").await.unwrap(), "Euro");
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+
+    let response = boi.resolve_currency_name("XXX").await;
+    assert!(
+        matches!(&response, Err(BancaDItaliaError::CurrencyNotFound(iso)) if iso == "XXX"),
+        "Error: {:#?}",
+        response
+    );
+    assert_eq!(requests.load(Ordering::SeqCst), 4);
+}
+
+#[tokio::test]
+async fn test_on_request_hook() {
+    let recorded = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&recorded);
+    let boi = BancaDItalia::builder()
+        .on_request(Box::new(move |metrics: RequestMetrics| {
+            sink.lock().unwrap().push(metrics)
+        }))
+        .build_with_mock(mock_transport());
+    let response = boi.get_latest_rate().await;
+    assert!(response.is_ok(), "Error: {:#?}", response);
+
+    let recorded = recorded.lock().unwrap();
+    assert_eq!(recorded.len(), 1);
+    assert_eq!(recorded[0].endpoint, "latestRates");
+    assert_eq!(recorded[0].status, Some(200));
+    assert!(recorded[0].bytes > 0);
+}
+
+#[cfg(feature = "reqwest-middleware")]
+#[tokio::test]
+async fn test_with_middleware() {
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let boi = BancaDItalia::with_middleware(client, Some(serve_latest_rates()));
+    let response = boi.get_latest_rate().await;
+    assert!(response.is_ok(), "Error: {:#?}", response);
+}
+
+#[tokio::test]
+#[cfg(feature = "usd")]
+async fn test_cross_rate_consistency() {
+    let boi = mock_client();
+    let response = boi.cross_rate_consistency("GBP", "JPY").await;
+    assert!(response.is_ok(), "Error: {:#?}", response);
+    assert!(response.unwrap() < Decimal::new(1, 2));
+}
+
+#[test]
+fn test_validity_timeline() {
+    let country = |name: &str, start, end| Country {
+        currencyiso: "EUR".to_string(),
+        country: name.to_string(),
+        countryiso: None,
+        validity_start_date: start,
+        validity_end_date: end,
+    };
+    let currency = Currency {
+        countries: vec![
+            country("SLOVENIA", date!(2007 - 01 - 01), None),
+            country("ITALY", date!(1999 - 01 - 01), Some(date!(2005 - 12 - 31))),
+            country("ITALY", date!(2004 - 01 - 01), None),
+            country("GERMANY", date!(1999 - 01 - 01), None),
+        ],
+        isocode: "EUR".to_string(),
+        name: "Euro".to_string(),
+        graph: true,
+    };
+    assert_eq!(
+        currency.validity_timeline(),
+        vec![
+            (date!(1999 - 01 - 01), None, "GERMANY".to_string()),
+            (date!(1999 - 01 - 01), None, "ITALY".to_string()),
+            (date!(2007 - 01 - 01), None, "SLOVENIA".to_string()),
+        ]
+    );
+
+    let currency = Currency {
+        countries: vec![
+            country("ITALY", date!(2002 - 01 - 01), Some(date!(2010 - 12 - 31))),
+            country("ITALY", date!(1999 - 01 - 01), Some(date!(2001 - 12 - 31))),
+            country("ITALY", date!(2011 - 01 - 02), None),
+        ],
+        ..currency
+    };
+    assert_eq!(
+        currency.validity_timeline(),
+        vec![
+            (
+                date!(1999 - 01 - 01),
+                Some(date!(2010 - 12 - 31)),
+                "ITALY".to_string()
+            ),
+            (date!(2011 - 01 - 02), None, "ITALY".to_string()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_is_tradable() {
+    let boi = mock_client();
+    assert!(boi.is_tradable("usd").await.unwrap());
+    assert!(!boi.is_tradable("XXX").await.unwrap());
+}
+
+#[tokio::test]
+async fn test_invalid_argument() {
+    let boi = mock_client();
+    let response = boi.quote("", "USD").await;
+    assert!(
+        matches!(&response, Err(BancaDItaliaError::InvalidArgument { name, .. }) if name == "from"),
+        "Error: {:#?}",
+        response
+    );
+}
+
+#[tokio::test]
+async fn test_raw_response() {
+    let boi = mock_client();
+    let response = boi
+        .raw_response("latestRates", &[("lang", "en")])
+        .await
+        .unwrap();
+    assert!(response.status().is_success());
+    assert!(response.text().await.unwrap().contains("latestRates"));
+}
+
+#[test]
+#[cfg(feature = "usd")]
+fn test_as_pairs() {
+    use bank_of_italy_api::UsdExchangeConvention;
+
+    let result = BancaDItalia::latest_rate_from_file(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/latest_rates.json"
+    ))
+    .unwrap();
+    let rate = |iso: &str| result.iter().find(|rate| rate.isocode == iso).unwrap();
+
+    assert_eq!(
+        rate("GBP").as_pairs(),
+        vec![
+            RatePair {
+                base: "EUR".to_string(),
+                quote: "GBP".to_string(),
+                rate: Decimal::new(86753, 5),
+                convention: None,
+            },
+            RatePair {
+                base: "USD".to_string(),
+                quote: "GBP".to_string(),
+                rate: "0.7457677679".parse().unwrap(),
+                convention: Some(UsdExchangeConvention::DollarsPerCurrency),
+            },
+        ]
+    );
+    assert_eq!(
+        rate("JPY").as_pairs()[1],
+        RatePair {
+            base: "USD".to_string(),
+            quote: "JPY".to_string(),
+            rate: "147.61".parse().unwrap(),
+            convention: Some(UsdExchangeConvention::CurrencyPerDollar),
+        }
+    );
+    assert!(rate("ZWL").as_pairs().is_empty());
+}
+
+#[test]
+#[cfg(not(feature = "usd"))]
+fn test_as_pairs_without_usd() {
+    let result = BancaDItalia::latest_rate_from_file(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/latest_rates.json"
+    ))
+    .unwrap();
+    let gbp = result.iter().find(|rate| rate.isocode == "GBP").unwrap();
+
+    assert_eq!(
+        gbp.as_pairs(),
+        vec![RatePair {
+            base: "EUR".to_string(),
+            quote: "GBP".to_string(),
+            rate: Decimal::new(86753, 5),
+            convention: None,
+        }]
+    );
+    let json = serde_json::to_value(gbp).unwrap();
+    assert!(json.get("usdRate").is_none());
+    assert!(json.get("eurRate").is_some());
+}
+
+#[tokio::test]
+async fn test_self_test() {
+    let boi = mock_client();
+    let report = boi.self_test().await;
+    assert_eq!(report.endpoints.len(), 2);
+    assert!(report.is_healthy(), "Report: {:#?}", report);
+}
+
+#[tokio::test]
+async fn test_self_test_reports_mapping_failures() {
+    let latest_rates = r#"{
+        "resultsInfo": {"totalRecords": 1, "timezoneReference": "CET", "notice": ""},
+        "latestRates": [{
+            "country": "UNITED KINGDOM",
+            "currency": "Pound Sterling",
+            "isoCode": "GBP",
+            "uicCode": "2",
+            "eurRate": "0.86753",
+            "usdRate": "0.7457",
+            "usdExchangeConvention": "Quantity of currency for 1 Dollar",
+            "usdExchangeConventionCode": "C",
+            "referenceDate": "not a date"
+        }]
+    }"#;
+    let boi = BancaDItalia::with_mock(
+        MockTransport::new()
+            .with_response("currencies", 500, "Internal Server Error")
+            .with_response("latestRates", 200, latest_rates),
+    );
+    let report = boi.self_test().await;
+    assert!(!report.is_healthy());
+    assert!(
+        matches!(&report.endpoints[0].outcome, EndpointOutcome::RequestFailed(message) if message.contains("500")),
+        "Report: {:#?}",
+        report
+    );
+    assert!(
+        matches!(report.endpoints[1].outcome, EndpointOutcome::ParseFailed(_)),
+        "Report: {:#?}",
+        report
+    );
+}
+
+#[cfg(feature = "symbols")]
+#[test]
+fn test_currency_symbol() {
+    let currency = |isocode: &str| Currency {
+        countries: Vec::new(),
+        isocode: isocode.to_string(),
+        name: String::new(),
+        graph: false,
+    };
+    assert_eq!(currency("GBP").symbol(), Some("£"));
+    assert_eq!(currency("AFN").symbol(), None);
+}
+
+#[test]
+fn test_changed_since() {
+    let previous = BancaDItalia::latest_rate_from_file(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/latest_rates.json"
+    ))
+    .unwrap();
+    assert!(BancaDItalia::changed_since(&previous, &previous).is_empty());
+
+    let mut current = previous.clone();
+    current[2].eur_rate = Decimal::new(87, 2);
+    let changed = BancaDItalia::changed_since(&current, &previous[1..]);
+    let isocodes: Vec<&str> = changed.iter().map(|rate| rate.isocode.as_str()).collect();
+    assert_eq!(isocodes, vec!["AFN", "GBP"]);
+}
+
+#[tokio::test]
+async fn test_last_successful_fetch() {
+    let boi = mock_client();
+    assert!(boi.last_successful_fetch().is_none());
+    boi.get_latest_rate().await.unwrap();
+    assert!(boi.last_successful_fetch().is_some());
+}
+
+#[tokio::test]
+async fn test_clock_sets_last_successful_fetch() {
+    let clock = Arc::new(FixedClock::new(datetime!(2025-07-30 14:00 UTC)));
+    let boi = BancaDItalia::builder()
+        .clock(clock.clone())
+        .build_with_mock(mock_transport());
+    boi.get_latest_rate().await.unwrap();
+    assert_eq!(
+        boi.last_successful_fetch(),
+        Some(datetime!(2025-07-30 14:00 UTC))
+    );
+    clock.advance(time::Duration::minutes(5));
+    boi.get_currencies().await.unwrap();
+    assert_eq!(
+        boi.last_successful_fetch(),
+        Some(datetime!(2025-07-30 14:05 UTC))
+    );
+}
+
+#[tokio::test]
+async fn test_eur_quote_table() {
+    let table = mock_client().eur_quote_table().await.unwrap();
+    assert_eq!(table["EUR"], Decimal::ONE);
+    assert_eq!(table["GBP"], "1.1526978894".parse::<Decimal>().unwrap());
+    assert!(table["GBP"] > Decimal::ONE);
+    assert!(!table.contains_key("ZWL"));
+}
+
+#[tokio::test]
+async fn test_multi_base() {
+    let boi = mock_client();
+    let gbp = boi
+        .multi_base("gbp", &["eur", "USD", "JPY", "ZWL"])
+        .await
+        .unwrap();
+    assert_eq!(gbp.len(), 3);
+    assert_eq!(gbp["EUR"], Decimal::new(86753, 5));
+    assert_eq!(gbp["JPY"], Decimal::new(86753, 5) / Decimal::new(17172, 2));
+    #[cfg(feature = "usd")]
+    assert_eq!(gbp["USD"], "0.7457677679".parse::<Decimal>().unwrap());
+    #[cfg(not(feature = "usd"))]
+    assert_eq!(gbp["USD"], Decimal::new(86753, 5) / Decimal::new(11633, 4));
+
+    assert!(boi.multi_base("ZWL", &["EUR"]).await.unwrap().is_empty());
+    assert!(matches!(
+        boi.multi_base("GBP", &["XYZ"]).await,
+        Err(BancaDItaliaError::CurrencyNotFound(_))
+    ));
+}
+
+#[tokio::test]
+#[cfg(feature = "usd")]
+async fn test_implied_eur_usd() {
+    let implied = mock_client().implied_eur_usd().await.unwrap();
+    assert_eq!(implied.len(), 5);
+    assert!(!implied.contains_key("ZWL"));
+    assert!(implied
+        .values()
+        .all(|rate| rate.round_dp(4) == Decimal::new(11633, 4)));
+}
+
+#[tokio::test]
+async fn test_rank_by_eur_strength() {
+    let ranking = mock_client().rank_by_eur_strength().await.unwrap();
+    let isos: Vec<&str> = ranking.iter().map(|(iso, _)| iso.as_str()).collect();
+    assert_eq!(isos, vec!["GBP", "EUR", "USD", "AFN", "JPY"]);
+    assert_eq!(ranking[1].1, Decimal::ONE);
+    assert!(ranking.windows(2).all(|w| w[0].1 >= w[1].1));
+}
+
+#[tokio::test]
+async fn test_convert_via() {
+    let boi = mock_client();
+    let response = boi
+        .convert_via(Decimal::from(100), &["GBP", "EUR", "USD", "JPY"])
+        .await;
+    assert!(response.is_ok(), "Error: {:#?}", response);
+    assert!(response.unwrap() > Decimal::ZERO);
+
+    let response = boi
+        .convert_via(Decimal::from(100), &["GBP", "EUR", "XXX"])
+        .await;
+    assert!(
+        matches!(
+            &response,
+            Err(BancaDItaliaError::ConversionHop { hop: 2, .. })
+        ),
+        "Error: {:#?}",
+        response
+    );
+
+    let response = boi.convert_via(Decimal::MAX, &["EUR", "GBP", "JPY"]).await;
+    match response {
+        Err(BancaDItaliaError::ConversionHop {
+            hop, from, source, ..
+        }) => {
+            assert_eq!(hop, 2);
+            assert_eq!(from, "GBP");
+            assert!(matches!(*source, BancaDItaliaError::AmountOverflow { .. }));
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_mock_transport_unknown_endpoint() {
+    let boi = BancaDItalia::with_mock(MockTransport::new());
+    let response = boi.raw_response("dailyRates", &[]).await.unwrap();
+    assert_eq!(response.status().as_u16(), 404);
+    assert!(boi.last_successful_fetch().is_none());
+}
+
+#[tokio::test]
+async fn test_value_portfolio() {
+    let boi = mock_client();
+    let holdings = [(Decimal::from(100), "EUR"), (Decimal::new(86753, 3), "GBP")];
+    let total = boi.value_portfolio(&holdings, "EUR").await.unwrap();
+    assert_eq!(total.round_dp(6), Decimal::from(200));
+
+    let response = boi.value_portfolio(&[(Decimal::ONE, "ZWL")], "EUR").await;
+    assert!(
+        matches!(&response, Err(BancaDItaliaError::RateUnavailable { iso, .. }) if iso == "ZWL"),
+        "Error: {:#?}",
+        response
+    );
+
+    let response = boi
+        .value_portfolio(&[(Decimal::ONE, "EUR"), (Decimal::MAX, "gbp")], "JPY")
+        .await;
+    assert!(
+        matches!(&response, Err(BancaDItaliaError::AmountOverflow { iso }) if iso == "GBP"),
+        "Error: {:#?}",
+        response
+    );
+    let response = boi
+        .value_portfolio(&[(Decimal::MAX, "EUR"), (Decimal::MAX, "EUR")], "EUR")
+        .await;
+    assert!(
+        matches!(&response, Err(BancaDItaliaError::AmountOverflow { iso }) if iso == "EUR"),
+        "Error: {:#?}",
+        response
+    );
+}
+
+#[test]
+fn test_uic_code() {
+    let numeric = UicCode::from("242".to_string());
+    assert_eq!(numeric.as_str(), "242");
+    assert_eq!(numeric.as_u32(), Some(242));
+
+    let legacy = UicCode::from("X1".to_string());
+    assert_eq!(legacy.as_str(), "X1");
+    assert_eq!(legacy.as_u32(), None);
+
+    let json = serde_json::to_string(&numeric).unwrap();
+    assert_eq!(json, "\"242\"");
+    assert_eq!(serde_json::from_str::<UicCode>(&json).unwrap(), numeric);
+}
+
+#[tokio::test]
+async fn test_base_urls_fallback() {
+    let boi = BancaDItalia::builder()
+        .base_urls(vec![unreachable_url(), serve_latest_rates()])
+        .build()
+        .unwrap();
+    let response = boi.get_latest_rate().await;
+    assert!(response.is_ok(), "Error: {:#?}", response);
+    assert_eq!(response.unwrap().len(), 6);
+}
+
+#[tokio::test]
+async fn test_base_urls_all_unreachable() {
+    let boi = BancaDItalia::builder()
+        .base_urls(vec![unreachable_url(), unreachable_url()])
+        .build()
+        .unwrap();
+    let response = boi.get_latest_rate().await;
+    assert!(
+        matches!(&response, Err(BancaDItaliaError::HostsUnreachable { hosts, .. }) if hosts.len() == 2),
+        "Error: {:#?}",
+        response
+    );
+}
+
+#[test]
+fn test_registry_diff() {
+    let currency = |isocode: &str| Currency {
+        countries: Vec::new(),
+        isocode: isocode.to_string(),
+        name: String::new(),
+        graph: false,
+    };
+    let old = vec![currency("ADP"), currency("EUR"), currency("ITL")];
+    let new = vec![currency("EUR"), currency("ZWG"), currency("AFN")];
+    assert_eq!(
+        BancaDItalia::registry_diff(&old, &new),
+        RegistryDiff {
+            added: vec!["AFN".to_string(), "ZWG".to_string()],
+            removed: vec!["ADP".to_string(), "ITL".to_string()],
+        }
+    );
+    assert!(BancaDItalia::registry_diff(&old, &old).is_empty());
+}
+
+#[tokio::test]
+async fn test_registry_rows() {
+    let currencies = mock_client().get_currencies().await.unwrap();
+    let rows = BancaDItalia::registry_rows(&currencies);
+    assert_eq!(rows.len(), 6);
+    assert_eq!(
+        rows[0],
+        RegistryRow {
+            isocode: "ADP".to_string(),
+            currency_name: "Andorran Peseta".to_string(),
+            country: "ANDORRA".to_string(),
+            country_iso: Some("AND".to_string()),
+            validity_start_date: date!(1936 - 01 - 01),
+            validity_end_date: Some(date!(2002 - 02 - 28)),
+        }
+    );
+    let emu = rows
+        .iter()
+        .find(|row| row.country == "EUROPEAN MONETARY UNION")
+        .unwrap();
+    assert_eq!(emu.isocode, "EUR");
+    assert_eq!(emu.country_iso, None);
+    assert_eq!(emu.validity_end_date, None);
+    assert_eq!(rows.iter().filter(|row| row.isocode == "EUR").count(), 3);
+
+    let gold = Currency {
+        countries: Vec::new(),
+        isocode: "XAU".to_string(),
+        name: "Gold".to_string(),
+        graph: false,
+    };
+    assert!(BancaDItalia::registry_rows(&[gold]).is_empty());
+}
+
+#[test]
+fn test_latest_rate_json_preserves_scale() {
+    let mut rate = BancaDItalia::latest_rate_from_file(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/latest_rates.json"
+    ))
+    .unwrap()
+    .remove(0);
+    rate.eur_rate = Decimal::new(772500, 4);
+    let json = rate.to_json().unwrap();
+    assert!(json.contains("\"eurRate\":\"77.2500\""), "JSON: {}", json);
+    let parsed = LatestRate::from_json(&json).unwrap();
+    assert_eq!(parsed.eur_rate.to_string(), "77.2500");
+    assert_eq!(parsed.eur_rate.scale(), 4);
+}
+
+#[test]
+fn test_result_info_offset() {
+    let info = |reference: &str| ResultInfo {
+        total_records: 0,
+        timezone_reference: reference.to_string(),
+        notice: String::new(),
+    };
+    let cet = info("Dates refer to the Central European Time Zone");
+    assert_eq!(cet.offset().unwrap(), offset!(+1));
+    assert_eq!(cet.offset_on(date!(2025 - 01 - 15)).unwrap(), offset!(+1));
+    assert_eq!(cet.offset_on(date!(2025 - 03 - 29)).unwrap(), offset!(+1));
+    assert_eq!(cet.offset_on(date!(2025 - 03 - 30)).unwrap(), offset!(+2));
+    assert_eq!(cet.offset_on(date!(2025 - 07 - 30)).unwrap(), offset!(+2));
+    assert_eq!(cet.offset_on(date!(2025 - 10 - 26)).unwrap(), offset!(+1));
+
+    assert_eq!(info("CET").offset().unwrap(), offset!(+1));
+    assert_eq!(info("CEST").offset().unwrap(), offset!(+2));
+    assert_eq!(
+        info("CEST").offset_on(date!(2025 - 01 - 15)).unwrap(),
+        offset!(+2)
+    );
+    assert!(matches!(
+        info("Pacific Time").offset(),
+        Err(BancaDItaliaError::ApiError(_))
+    ));
+}
+
+#[test]
+fn test_country_is_eurozone() {
+    let italy = Country {
+        currencyiso: "EUR".to_string(),
+        country: "ITALY".to_string(),
+        countryiso: Some("ITA".to_string()),
+        validity_start_date: date!(1999 - 01 - 01),
+        validity_end_date: None,
+    };
+    assert!(italy.is_eurozone());
+
+    let lira = Country {
+        currencyiso: "ITL".to_string(),
+        validity_start_date: date!(1861 - 03 - 17),
+        validity_end_date: Some(date!(2002 - 02 - 28)),
+        ..italy
+    };
+    assert!(!lira.is_eurozone());
+}
+
+#[tokio::test]
+async fn test_unexpected_redirect() {
+    let boi = BancaDItalia::with_mock(MockTransport::new().with_response("latestRates", 302, ""));
+    let response = boi.get_latest_rate().await;
+    assert!(
+        matches!(&response, Err(BancaDItaliaError::UnexpectedRedirect { .. })),
+        "Error: {:#?}",
+        response
+    );
+}